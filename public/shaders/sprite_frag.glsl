@@ -0,0 +1,13 @@
+#version 300 es
+
+precision mediump float;
+
+uniform sampler2D image;
+
+in vec2 vTexCoord;
+in vec4 vColor;
+out vec4 outColor;
+
+void main() {
+    outColor = texture(image, vTexCoord) * vColor;
+}
\ No newline at end of file
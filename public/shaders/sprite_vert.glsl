@@ -0,0 +1,21 @@
+#version 300 es
+layout(location = 0) in vec2 aPosition;
+layout(location = 1) in vec2 aTexCoord;
+layout(location = 2) in vec4 aColor;
+
+uniform struct Camera {
+    vec2 position;
+    float zoom;
+} camera;
+
+out vec2 vTexCoord;
+out vec4 vColor;
+
+void main() {
+    vec2 finalPosition = (aPosition - camera.position) * camera.zoom;                            // Quads arrive already placed
+
+    vTexCoord = vec2(aTexCoord.x, 1.0 - aTexCoord.y);
+    vColor = aColor;
+
+    gl_Position = vec4(finalPosition, 0.0, 1.0);
+}
\ No newline at end of file
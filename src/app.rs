@@ -0,0 +1,30 @@
+use crate::engine::game_loop::GameLoop;
+use crate::engine::object::snake_game::SnakeGame;
+use crate::engine::object_manager::ObjectManager;
+use crate::engine::*;
+use crate::object;
+use std::cell::RefCell;
+use std::rc::Rc;
+use wasm_bindgen::prelude::*;
+
+#[wasm_bindgen(start)]
+async fn run() -> Result<(), JsValue> {
+    let mut ctx = Engine::init().await;
+    let ctx = &mut ctx;
+
+    let snake = SnakeGame::new(ctx, [1000.0, 1000.0].into()).await;
+    object!(ctx, snake);
+    ObjectManager::apply_pending(ctx);
+
+    let mut game_loop = GameLoop::new(120.0);
+    game_loop.clear_color = [0.1, 0.1, 0.1, 1.0];
+
+    loop {
+        let timestamp = web::next_animation_frame().await;
+        game_loop.frame(ctx, timestamp);
+
+        if ctx.input.action_down("quit") {
+            exit();
+        }
+    }
+}
@@ -10,6 +10,22 @@ impl Camera {
         Self { position, zoom }
     }
 
+    /// Converts a point in screen coordinates (1920x1080, origin top left, y
+    /// down) to the world position drawn there.
+    pub fn screen_to_world(&self, screen: &Vec2f) -> Vec2f {
+        Vec2f::new(
+            (screen.x - 960.0) / self.zoom + self.position.x,
+            (540.0 - screen.y) / self.zoom + self.position.y,
+        )
+    }
+
+    pub fn world_to_screen(&self, world: &Vec2f) -> Vec2f {
+        Vec2f::new(
+            960.0 + (world.x - self.position.x) * self.zoom,
+            540.0 - (world.y - self.position.y) * self.zoom,
+        )
+    }
+
     pub fn tick(&mut self) {
         // self.position = om().objects[0].borrow().transform().position.clone();
     }
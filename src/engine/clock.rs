@@ -0,0 +1,46 @@
+use crate::engine::web;
+use std::cell::Cell;
+use std::rc::Rc;
+
+/// A source of time in milliseconds.
+pub trait Clock {
+    fn now(&self) -> f64;
+}
+
+/// Real time from `performance.now()`. Outside the browser it reads the
+/// system clock instead.
+#[derive(Clone, Copy, Default)]
+pub struct BrowserClock;
+
+impl Clock for BrowserClock {
+    fn now(&self) -> f64 {
+        web::now()
+    }
+}
+
+/// A clock that only moves when told to. Clones share the same time, so one
+/// handle can drive every timer made from the others.
+#[derive(Clone, Default)]
+pub struct ManualClock {
+    now: Rc<Cell<f64>>,
+}
+
+impl ManualClock {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn advance(&self, milliseconds: f64) {
+        self.now.set(self.now.get() + milliseconds);
+    }
+
+    pub fn set(&self, milliseconds: f64) {
+        self.now.set(milliseconds);
+    }
+}
+
+impl Clock for ManualClock {
+    fn now(&self) -> f64 {
+        self.now.get()
+    }
+}
@@ -0,0 +1,86 @@
+use crate::engine::object_manager::ObjectId;
+use crate::engine::vec2f::Vec2f;
+use std::collections::HashSet;
+
+/// Two objects, the lower id first.
+pub type Pair = (ObjectId, ObjectId);
+
+/// How two overlapping shapes meet. The normal is a unit vector pointing
+/// from the first shape towards the second: moving the first by
+/// `-normal * depth`, or the second by `normal * depth`, separates them.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Manifold {
+    pub normal: Vec2f,
+    pub depth: f64,
+    /// Corners of either shape lying inside the other, or the midpoint
+    /// between their centers when none do.
+    pub points: Vec<Vec2f>,
+}
+
+impl Manifold {
+    /// The same contact seen from the second shape.
+    pub fn flipped(&self) -> Self {
+        Self {
+            normal: &self.normal * -1.0,
+            depth: self.depth,
+            points: self.points.clone(),
+        }
+    }
+}
+
+/// Where a moving shape first touches another, from `Collider::sweep` or
+/// `Transform::sweep`.
+#[derive(Clone, PartialEq, Debug)]
+pub struct SweepHit {
+    /// Which of the shapes swept against was hit.
+    pub index: usize,
+    /// How far along the movement contact happens, from 0 to 1.
+    pub time: f64,
+    /// Unit vector pointing from the moving shape towards the one it hit.
+    pub normal: Vec2f,
+}
+
+/// A contact handed to `Object::on_collision_enter` and `on_collision_stay`,
+/// seen from the object receiving it.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Collision {
+    pub other: ObjectId,
+    pub manifold: Manifold,
+}
+
+/// The pairs of colliding objects found by the last collision pass, so the
+/// next one can tell which contacts began and which ended.
+pub struct Contacts {
+    pairs: HashSet<Pair>,
+}
+
+impl Contacts {
+    pub fn new() -> Self {
+        Self {
+            pairs: HashSet::new(),
+        }
+    }
+
+    /// Replaces the remembered pairs with `current`, returning the pairs
+    /// that are new, and the pairs that ended in id order.
+    pub fn update(
+        &mut self,
+        current: &[(ObjectId, ObjectId, Manifold)],
+    ) -> (HashSet<Pair>, Vec<Pair>) {
+        let current: HashSet<Pair> = current.iter().map(|(a, b, _)| (*a, *b)).collect();
+
+        let mut ended: Vec<Pair> = self.pairs.difference(&current).cloned().collect();
+        ended.sort_unstable();
+
+        let began = current.difference(&self.pairs).cloned().collect();
+        self.pairs = current;
+
+        (began, ended)
+    }
+}
+
+impl Default for Contacts {
+    fn default() -> Self {
+        Self::new()
+    }
+}
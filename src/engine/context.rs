@@ -0,0 +1,51 @@
+use crate::engine::clock::ManualClock;
+use crate::engine::input::recording::InputRecording;
+use crate::engine::input::Input;
+use crate::engine::object_manager::ObjectManager;
+use crate::engine::renderer::Renderer;
+use crate::engine::rng::Rng;
+use crate::engine::shader_manager::ShaderManager;
+use crate::engine::sprite_batch::SpriteBatch;
+use crate::engine::text_renderer::TextRenderer;
+use crate::engine::texture_manager::TextureManager;
+
+/// Everything a running engine owns. Created by `Engine::init` and handed to
+/// objects as they are initialized, ticked and drawn.
+pub struct EngineContext {
+    pub renderer: Box<dyn Renderer>,
+    pub om: ObjectManager,
+    pub sm: ShaderManager,
+    pub tm: TextureManager,
+    pub tr: TextRenderer,
+    /// Where rects queue their quads while drawing, to be drawn together.
+    pub batch: SpriteBatch,
+    pub input: Input,
+    /// Simulated time, advanced by one step's worth each update. Timers
+    /// built on it follow the simulation rather than the wall clock.
+    pub clock: ManualClock,
+    /// Seed this for repeatable games and snapshot it to replay them.
+    pub rng: Rng,
+}
+
+impl EngineContext {
+    /// Starts recording input, noting the RNG state so a replay draws the
+    /// same numbers.
+    pub fn start_recording(&mut self) {
+        self.input
+            .start_recording(InputRecording::new(Some(self.rng.snapshot())));
+    }
+
+    pub fn stop_recording(&mut self) -> Option<InputRecording> {
+        self.input.stop_recording()
+    }
+
+    /// Rewinds the RNG to where `recording` started and feeds its input back
+    /// frame by frame.
+    pub fn replay(&mut self, recording: &InputRecording) {
+        if let Some(state) = recording.rng {
+            self.rng.restore(state);
+        }
+
+        self.input.replay(recording);
+    }
+}
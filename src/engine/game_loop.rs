@@ -0,0 +1,110 @@
+use crate::engine::context::EngineContext;
+use crate::engine::object_manager::ObjectManager;
+
+/// Runs the simulation at a fixed rate regardless of how often frames are
+/// drawn. Real time is banked in an accumulator and spent in whole steps; the
+/// leftover fraction of a step is exposed to drawing as an interpolation alpha.
+pub struct GameLoop {
+    /// Seconds of simulated time per step.
+    pub step: f64,
+    /// Most steps run in one frame. Time beyond that is dropped so a long
+    /// hitch doesn't snowball into ever longer frames.
+    pub max_steps: u32,
+    pub clear_color: [f32; 4],
+    accumulator: f64,
+    last_timestamp: Option<f64>,
+    paused: bool,
+    steps: u64,
+}
+
+impl GameLoop {
+    pub fn new(steps_per_second: f64) -> Self {
+        Self {
+            step: 1.0 / steps_per_second,
+            max_steps: 8,
+            clear_color: [0.0, 0.0, 0.0, 1.0],
+            accumulator: 0.0,
+            last_timestamp: None,
+            paused: false,
+            steps: 0,
+        }
+    }
+
+    pub fn pause(&mut self) {
+        self.paused = true;
+    }
+
+    /// Carries on from where the simulation was paused, without catching up
+    /// on the time spent paused.
+    pub fn resume(&mut self) {
+        self.paused = false;
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.paused
+    }
+
+    /// Number of simulation steps run so far.
+    pub fn steps(&self) -> u64 {
+        self.steps
+    }
+
+    /// Runs one frame for a `requestAnimationFrame` timestamp in milliseconds:
+    /// advances the simulation and draws.
+    pub fn frame(&mut self, ctx: &mut EngineContext, timestamp: f64) {
+        let elapsed = match self.last_timestamp {
+            None => 0.0,
+            Some(last_timestamp) => (timestamp - last_timestamp) / 1000.0,
+        };
+        self.last_timestamp = Some(timestamp);
+
+        let alpha = self.advance(ctx, elapsed);
+        self.render(ctx, alpha);
+    }
+
+    /// Banks `elapsed` seconds and runs as many fixed steps as they pay for.
+    /// Returns the interpolation alpha, in `[0, 1)`.
+    pub fn advance(&mut self, ctx: &mut EngineContext, elapsed: f64) -> f64 {
+        if self.paused {
+            return self.alpha();
+        }
+
+        self.accumulator += elapsed.max(0.0);
+
+        let mut steps = 0;
+        while self.accumulator >= self.step && steps < self.max_steps {
+            self.step_once(ctx);
+            self.accumulator -= self.step;
+            steps += 1;
+        }
+
+        if self.accumulator >= self.step {
+            self.accumulator %= self.step;
+        }
+
+        self.alpha()
+    }
+
+    /// Runs exactly one simulation step, ignoring the accumulator and pause
+    /// state. Useful for stepping a scene deterministically.
+    pub fn step_once(&mut self, ctx: &mut EngineContext) {
+        ctx.clock.advance(self.step * 1000.0);
+        ctx.input.poll_events(self.step);
+        ObjectManager::tick(ctx, self.step);
+        ObjectManager::apply_pending(ctx);
+        ctx.input.flush_pressed_map();
+
+        self.steps += 1;
+    }
+
+    pub fn render(&self, ctx: &mut EngineContext, alpha: f64) {
+        ctx.om.interpolation_alpha = alpha;
+
+        ctx.renderer.clear(self.clear_color);
+        ObjectManager::draw(ctx);
+    }
+
+    fn alpha(&self) -> f64 {
+        self.accumulator / self.step
+    }
+}
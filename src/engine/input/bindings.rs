@@ -0,0 +1,183 @@
+use crate::engine::web;
+use std::collections::HashMap;
+
+const DEFAULT_BINDINGS: &str = include_str!("../../../public/bindings.txt");
+
+/// An axis driven by a pair of keys: -1 while `negative` is held, 1 while
+/// `positive` is.
+#[derive(Clone, PartialEq, Debug)]
+pub struct KeyAxis {
+    pub negative: String,
+    pub positive: String,
+}
+
+/// Maps named actions and axes to the keys that drive them, so games ask for
+/// `"fire"` rather than `"KeyF"` and players can rebind.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Bindings {
+    actions: HashMap<String, Vec<String>>,
+    axes: HashMap<String, Vec<KeyAxis>>,
+    analog: HashMap<String, Vec<String>>,
+}
+
+impl Bindings {
+    /// The engine's default bindings, from `public/bindings.txt`.
+    pub fn new() -> Self {
+        Self::parse(DEFAULT_BINDINGS).expect("Default bindings should parse.")
+    }
+
+    pub fn empty() -> Self {
+        Self {
+            actions: HashMap::new(),
+            axes: HashMap::new(),
+            analog: HashMap::new(),
+        }
+    }
+
+    pub async fn load(url: &str) -> Result<Self, String> {
+        Self::parse(&web::get_string(url).await?)
+    }
+
+    /// Reads `action <name> <code>...`, `axis <name> <negative> <positive>`
+    /// and `analog <axis> <source>...` lines. Blank lines and lines starting
+    /// with `#` are skipped.
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let mut bindings = Self::empty();
+
+        for line in text.lines().map(str::trim) {
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            match fields.as_slice() {
+                ["action", name, codes @ ..] if !codes.is_empty() => {
+                    for code in codes {
+                        bindings.bind_action(name, code);
+                    }
+                }
+                ["axis", name, negative, positive] => {
+                    bindings.bind_axis(name, negative, positive);
+                }
+                ["analog", name, sources @ ..] if !sources.is_empty() => {
+                    for source in sources {
+                        bindings.bind_analog(name, source);
+                    }
+                }
+                _ => return Err(format!("Bad binding `{line}`")),
+            }
+        }
+
+        Ok(bindings)
+    }
+
+    /// Writes the bindings back out in the format `parse` reads, sorted by
+    /// name.
+    pub fn serialize(&self) -> String {
+        let mut out = String::new();
+
+        let mut actions: Vec<_> = self.actions.iter().collect();
+        actions.sort();
+        for (name, codes) in actions {
+            if !codes.is_empty() {
+                out.push_str(&format!("action {name} {}\n", codes.join(" ")));
+            }
+        }
+
+        let mut axes: Vec<_> = self.axes.iter().collect();
+        axes.sort_by(|a, b| a.0.cmp(b.0));
+        for (name, keys) in axes {
+            for key in keys {
+                out.push_str(&format!("axis {name} {} {}\n", key.negative, key.positive));
+            }
+        }
+
+        let mut analog: Vec<_> = self.analog.iter().collect();
+        analog.sort();
+        for (name, sources) in analog {
+            if !sources.is_empty() {
+                out.push_str(&format!("analog {name} {}\n", sources.join(" ")));
+            }
+        }
+
+        out
+    }
+
+    /// Adds `code` to the keys that trigger `action`.
+    pub fn bind_action(&mut self, action: &str, code: &str) {
+        let codes = self.actions.entry(action.to_string()).or_default();
+        if !codes.iter().any(|c| c == code) {
+            codes.push(code.to_string());
+        }
+    }
+
+    /// Makes `code` the only key that triggers `action`.
+    pub fn rebind_action(&mut self, action: &str, code: &str) {
+        self.unbind_action(action);
+        self.bind_action(action, code);
+    }
+
+    pub fn unbind_action(&mut self, action: &str) {
+        self.actions.remove(action);
+    }
+
+    pub fn action_keys(&self, action: &str) -> &[String] {
+        match self.actions.get(action) {
+            None => &[],
+            Some(codes) => codes,
+        }
+    }
+
+    pub fn bind_axis(&mut self, axis: &str, negative: &str, positive: &str) {
+        self.axes
+            .entry(axis.to_string())
+            .or_default()
+            .push(KeyAxis {
+                negative: negative.to_string(),
+                positive: positive.to_string(),
+            });
+    }
+
+    /// Makes `negative`/`positive` the only keys that drive `axis`.
+    pub fn rebind_axis(&mut self, axis: &str, negative: &str, positive: &str) {
+        self.unbind_axis(axis);
+        self.bind_axis(axis, negative, positive);
+    }
+
+    pub fn unbind_axis(&mut self, axis: &str) {
+        self.axes.remove(axis);
+    }
+
+    pub fn axis_keys(&self, axis: &str) -> &[KeyAxis] {
+        match self.axes.get(axis) {
+            None => &[],
+            Some(keys) => keys,
+        }
+    }
+
+    /// Adds an analog source, such as a virtual joystick's `JoystickX`, to
+    /// the inputs summed into `axis`.
+    pub fn bind_analog(&mut self, axis: &str, source: &str) {
+        let sources = self.analog.entry(axis.to_string()).or_default();
+        if !sources.iter().any(|s| s == source) {
+            sources.push(source.to_string());
+        }
+    }
+
+    pub fn unbind_analog(&mut self, axis: &str) {
+        self.analog.remove(axis);
+    }
+
+    pub fn analog_sources(&self, axis: &str) -> &[String] {
+        match self.analog.get(axis) {
+            None => &[],
+            Some(sources) => sources,
+        }
+    }
+}
+
+impl Default for Bindings {
+    fn default() -> Self {
+        Self::new()
+    }
+}
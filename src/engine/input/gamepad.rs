@@ -0,0 +1,210 @@
+use crate::engine::input::InputEvent;
+use crate::engine::vec2f::Vec2f;
+
+/// Key codes for the buttons of the browser's standard gamepad layout.
+/// Buttons past the end are `GamepadButton<index>`.
+const BUTTON_CODES: [&str; 17] = [
+    "GamepadA",
+    "GamepadB",
+    "GamepadX",
+    "GamepadY",
+    "GamepadLeftBumper",
+    "GamepadRightBumper",
+    "GamepadLeftTrigger",
+    "GamepadRightTrigger",
+    "GamepadSelect",
+    "GamepadStart",
+    "GamepadLeftStick",
+    "GamepadRightStick",
+    "GamepadUp",
+    "GamepadDown",
+    "GamepadLeft",
+    "GamepadRight",
+    "GamepadHome",
+];
+
+pub const LEFT_TRIGGER: usize = 6;
+pub const RIGHT_TRIGGER: usize = 7;
+
+/// The key code a gamepad button presses.
+pub fn button_code(button: usize) -> String {
+    match BUTTON_CODES.get(button) {
+        Some(code) => code.to_string(),
+        None => format!("GamepadButton{button}"),
+    }
+}
+
+/// A gamepad's raw readings, as the browser reports them.
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct GamepadState {
+    pub buttons: Vec<f64>,
+    pub axes: Vec<f64>,
+}
+
+impl GamepadState {
+    pub fn button(&self, button: usize) -> f64 {
+        *self.buttons.get(button).unwrap_or(&0.0)
+    }
+
+    pub fn axis(&self, axis: usize) -> f64 {
+        *self.axes.get(axis).unwrap_or(&0.0)
+    }
+}
+
+/// Every gamepad slot the browser exposes, plus which connected or
+/// disconnected this frame.
+pub struct Gamepads {
+    pub pads: Vec<Option<GamepadState>>,
+    pub connected: Vec<usize>,
+    pub disconnected: Vec<usize>,
+    /// Stick deflection, as a fraction of full, below which a stick reads 0.
+    pub stick_deadzone: f64,
+    /// Trigger travel below which a trigger reads 0.
+    pub trigger_deadzone: f64,
+    /// Value past which a button counts as held.
+    pub button_threshold: f64,
+}
+
+impl Gamepads {
+    pub fn new() -> Self {
+        Self {
+            pads: Vec::new(),
+            connected: Vec::new(),
+            disconnected: Vec::new(),
+            stick_deadzone: 0.2,
+            trigger_deadzone: 0.1,
+            button_threshold: 0.5,
+        }
+    }
+
+    pub fn get(&self, pad: usize) -> Option<&GamepadState> {
+        self.pads.get(pad).and_then(|pad| pad.as_ref())
+    }
+
+    pub fn apply(&mut self, event: &InputEvent) {
+        match *event {
+            InputEvent::GamepadConnected { pad } => {
+                if self.pads.len() <= pad {
+                    self.pads.resize(pad + 1, None);
+                }
+                self.pads[pad] = Some(GamepadState::default());
+                self.connected.push(pad);
+            }
+            InputEvent::GamepadDisconnected { pad } => {
+                if let Some(slot) = self.pads.get_mut(pad) {
+                    *slot = None;
+                }
+                self.disconnected.push(pad);
+            }
+            InputEvent::GamepadButton { pad, button, value } => {
+                if let Some(Some(state)) = self.pads.get_mut(pad) {
+                    set(&mut state.buttons, button, value);
+                }
+            }
+            InputEvent::GamepadAxis { pad, axis, value } => {
+                if let Some(Some(state)) = self.pads.get_mut(pad) {
+                    set(&mut state.axes, axis, value);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    pub fn flush(&mut self) {
+        self.connected.clear();
+        self.disconnected.clear();
+    }
+
+    /// The events that take this state to `snapshot`, the browser's current
+    /// readings. Values are rounded so sensor noise doesn't flood recordings.
+    pub fn diff(&self, snapshot: &[Option<GamepadState>]) -> Vec<InputEvent> {
+        let mut events = Vec::new();
+        let empty = GamepadState::default();
+
+        for pad in 0..self.pads.len().max(snapshot.len()) {
+            let old = self.get(pad);
+            let new = snapshot.get(pad).and_then(|pad| pad.as_ref());
+
+            let new = match (old, new) {
+                (None, None) => continue,
+                (Some(_), None) => {
+                    events.push(InputEvent::GamepadDisconnected { pad });
+                    continue;
+                }
+                (None, Some(new)) => {
+                    events.push(InputEvent::GamepadConnected { pad });
+                    new
+                }
+                (Some(_), Some(new)) => new,
+            };
+            let old = old.unwrap_or(&empty);
+
+            for (button, value) in new.buttons.iter().enumerate() {
+                let value = quantize(*value);
+                if value != old.button(button) {
+                    events.push(InputEvent::GamepadButton { pad, button, value });
+                }
+            }
+
+            for (axis, value) in new.axes.iter().enumerate() {
+                let value = quantize(*value);
+                if value != old.axis(axis) {
+                    events.push(InputEvent::GamepadAxis { pad, axis, value });
+                }
+            }
+        }
+
+        events
+    }
+
+    pub fn pressed(&self, pad: usize, button: usize) -> bool {
+        self.get(pad)
+            .is_some_and(|state| state.button(button) > self.button_threshold)
+    }
+
+    /// A stick with the deadzone cut out and the rest rescaled to `[0, 1]`.
+    /// Stick 0 is the left stick, 1 the right. Up is positive y, as in the
+    /// world.
+    pub fn stick(&self, pad: usize, stick: usize) -> Vec2f {
+        let state = match self.get(pad) {
+            None => return Vec2f::new(0.0, 0.0),
+            Some(state) => state,
+        };
+
+        let raw = Vec2f::new(state.axis(stick * 2), -state.axis(stick * 2 + 1));
+        let len = raw.len();
+        if len <= self.stick_deadzone {
+            return Vec2f::new(0.0, 0.0);
+        }
+
+        let scaled = ((len - self.stick_deadzone) / (1.0 - self.stick_deadzone)).min(1.0);
+        &raw.normalize() * scaled
+    }
+
+    /// A trigger's travel in `[0, 1]` with the deadzone cut out.
+    pub fn trigger(&self, pad: usize, button: usize) -> f64 {
+        let value = self.get(pad).map_or(0.0, |state| state.button(button));
+        if value <= self.trigger_deadzone {
+            return 0.0;
+        }
+
+        ((value - self.trigger_deadzone) / (1.0 - self.trigger_deadzone)).min(1.0)
+    }
+}
+
+impl Default for Gamepads {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn set(values: &mut Vec<f64>, index: usize, value: f64) {
+    if values.len() <= index {
+        values.resize(index + 1, 0.0);
+    }
+    values[index] = value;
+}
+
+fn quantize(value: f64) -> f64 {
+    (value * 1000.0).round() / 1000.0
+}
@@ -0,0 +1,512 @@
+use crate::engine::input::bindings::Bindings;
+use crate::engine::input::gamepad::{button_code, Gamepads, LEFT_TRIGGER, RIGHT_TRIGGER};
+use crate::engine::input::pointer::Pointer;
+use crate::engine::input::recording::{InputRecording, InputReplay};
+use crate::engine::input::source::{EventQueue, InputSource};
+use crate::engine::input::touch::{TouchPhase, Touches};
+use crate::engine::vec2f::Vec2f;
+#[cfg(feature = "web")]
+use crate::engine::web;
+use std::collections::{HashMap, HashSet};
+
+pub mod bindings;
+pub mod gamepad;
+pub mod pointer;
+pub mod recording;
+pub mod source;
+pub mod touch;
+
+/// Something the player did, as reported by the DOM. Pointer positions are
+/// in screen coordinates (see `Pointer`).
+#[derive(Clone, PartialEq, Debug)]
+pub enum InputEvent {
+    Key {
+        code: String,
+        down: bool,
+    },
+    /// A held key auto-repeating.
+    KeyRepeat {
+        code: String,
+    },
+    /// A character typed, as `KeyboardEvent.key` gives it.
+    Text {
+        character: char,
+    },
+    PointerMove {
+        x: f64,
+        y: f64,
+    },
+    PointerButton {
+        button: u16,
+        down: bool,
+    },
+    Wheel {
+        delta: f64,
+    },
+    Touch {
+        id: i32,
+        phase: TouchPhase,
+        x: f64,
+        y: f64,
+    },
+    GamepadConnected {
+        pad: usize,
+    },
+    GamepadDisconnected {
+        pad: usize,
+    },
+    GamepadButton {
+        pad: usize,
+        button: usize,
+        value: f64,
+    },
+    GamepadAxis {
+        pad: usize,
+        axis: usize,
+        value: f64,
+    },
+}
+
+/// Which modifier keys are held, either side counting.
+#[derive(Clone, Copy, PartialEq, Debug, Default)]
+pub struct Modifiers {
+    pub shift: bool,
+    pub control: bool,
+    pub alt: bool,
+    pub meta: bool,
+}
+
+// One step of this frame's typing, kept in the order it happened.
+#[derive(Clone, Copy)]
+enum TextEdit {
+    Type(char),
+    Delete,
+}
+
+pub struct Input {
+    pub key_map: HashMap<String, bool>,
+    pub key_pressed_map: HashMap<String, bool>,
+    pub key_released_map: HashMap<String, bool>,
+    pub key_repeated_map: HashMap<String, bool>,
+    pub bindings: Bindings,
+    pub pointer: Pointer,
+    pub touches: Touches,
+    pub gamepads: Gamepads,
+    text: String,
+    edits: Vec<TextEdit>,
+    virtual_keys: HashMap<String, bool>,
+    analog_values: HashMap<String, f64>,
+    queue: EventQueue,
+    source: Box<dyn InputSource>,
+    recording: Option<InputRecording>,
+    #[cfg_attr(not(feature = "web"), allow(dead_code))]
+    listening: bool,
+    replaying: bool,
+}
+
+impl Input {
+    /// Creates empty key maps. Nothing arrives until `listen` is called or
+    /// events are pushed.
+    pub fn new() -> Self {
+        let key_map: HashMap<String, bool> = HashMap::new();
+        let key_pressed_map: HashMap<String, bool> = HashMap::new();
+        let key_released_map: HashMap<String, bool> = HashMap::new();
+        let key_repeated_map: HashMap<String, bool> = HashMap::new();
+        let queue = EventQueue::new();
+
+        Self {
+            key_map,
+            key_pressed_map,
+            key_released_map,
+            key_repeated_map,
+            bindings: Bindings::new(),
+            pointer: Pointer::new(),
+            touches: Touches::new(),
+            gamepads: Gamepads::new(),
+            text: String::new(),
+            edits: Vec::new(),
+            virtual_keys: HashMap::new(),
+            analog_values: HashMap::new(),
+            queue: queue.clone(),
+            source: Box::new(queue),
+            recording: None,
+            listening: false,
+            replaying: false,
+        }
+    }
+
+    /// Registers keyboard listeners on the document and pointer and touch
+    /// listeners on the canvas with the given id.
+    /// Gamepads are polled from then on too.
+    #[cfg(feature = "web")]
+    pub fn listen(&mut self, canvas_id: &str) {
+        self.listening = true;
+        web::keyboard::listen(self.queue.clone());
+        web::pointer::listen(self.queue.clone(), canvas_id);
+        web::touch::listen(self.queue.clone(), canvas_id);
+    }
+
+    /// Queues an event as if it came from the DOM. It is applied, and
+    /// recorded, on the next `poll_events`.
+    pub fn push_event(&self, event: InputEvent) {
+        self.queue.push(event);
+    }
+
+    /// Applies the source's events for a frame lasting `delta_time` seconds.
+    pub fn poll_events(&mut self, delta_time: f64) {
+        #[cfg(feature = "web")]
+        if self.listening && !self.replaying {
+            for event in self.gamepads.diff(&web::gamepad::snapshot()) {
+                self.queue.push(event);
+            }
+        }
+
+        let events = self.source.poll();
+
+        if let Some(recording) = &mut self.recording {
+            recording.record(delta_time, &events);
+        }
+
+        self.touches.age(delta_time);
+        self.apply_virtual_keys();
+
+        for event in events {
+            match event {
+                InputEvent::Key { code, down: true } => self.key_down(code),
+                InputEvent::Key { code, down: false } => self.key_up(code),
+                InputEvent::KeyRepeat { code } => {
+                    if self.get_key_down(&code) {
+                        if code == "Backspace" {
+                            self.edits.push(TextEdit::Delete);
+                        }
+                        self.key_repeated_map.insert(code, true);
+                    }
+                }
+                InputEvent::Text { character } => {
+                    self.text.push(character);
+                    self.edits.push(TextEdit::Type(character));
+                }
+                InputEvent::PointerMove { x, y } => self.pointer.position = [x, y].into(),
+                InputEvent::PointerButton { button, down: true } => {
+                    self.pointer.button_down(button)
+                }
+                InputEvent::PointerButton {
+                    button,
+                    down: false,
+                } => self.pointer.button_up(button),
+                InputEvent::Wheel { delta } => self.pointer.wheel_delta += delta,
+                InputEvent::Touch { id, phase, x, y } => {
+                    self.touches.apply(id, phase, [x, y].into())
+                }
+                InputEvent::GamepadConnected { .. }
+                | InputEvent::GamepadDisconnected { .. }
+                | InputEvent::GamepadButton { .. }
+                | InputEvent::GamepadAxis { .. } => self.gamepads.apply(&event),
+            }
+        }
+
+        self.sync_gamepads();
+
+        // A swipe taps its key: pressed and released within the frame.
+        for swipe in self.touches.swipes.clone() {
+            let code = swipe.direction.key_code();
+            self.key_down(code.into());
+            self.key_up(code.into());
+        }
+    }
+
+    /// Holds or releases a key that no keyboard sends, such as an on-screen
+    /// d-pad's. The change shows up, with its pressed or released edge, from
+    /// the next frame.
+    pub fn set_virtual_key(&mut self, code: &str, down: bool) {
+        self.virtual_keys.insert(code.to_string(), down);
+    }
+
+    /// Sets an analog source that axes can be bound to, in `[-1, 1]`.
+    pub fn set_analog(&mut self, source: &str, value: f64) {
+        self.analog_values.insert(source.to_string(), value);
+    }
+
+    pub fn analog_value(&self, source: &str) -> f64 {
+        *self.analog_values.get(source).unwrap_or(&0.0)
+    }
+
+    // Holds each gamepad button's key while any pad holds the button, and
+    // publishes the sticks and triggers as analog sources.
+    fn sync_gamepads(&mut self) {
+        let mut held: HashSet<String> = HashSet::new();
+        let mut left = Vec2f::new(0.0, 0.0);
+        let mut right = Vec2f::new(0.0, 0.0);
+        let mut left_trigger: f64 = 0.0;
+        let mut right_trigger: f64 = 0.0;
+
+        for pad in 0..self.gamepads.pads.len() {
+            let button_count = match self.gamepads.get(pad) {
+                None => continue,
+                Some(state) => state.buttons.len(),
+            };
+
+            for button in 0..button_count {
+                if self.gamepads.pressed(pad, button) {
+                    held.insert(button_code(button));
+                }
+            }
+
+            let stick = self.gamepads.stick(pad, 0);
+            if stick.len() > left.len() {
+                left = stick;
+            }
+            let stick = self.gamepads.stick(pad, 1);
+            if stick.len() > right.len() {
+                right = stick;
+            }
+            left_trigger = left_trigger.max(self.gamepads.trigger(pad, LEFT_TRIGGER));
+            right_trigger = right_trigger.max(self.gamepads.trigger(pad, RIGHT_TRIGGER));
+        }
+
+        let released: Vec<String> = self
+            .key_map
+            .iter()
+            .filter(|(code, down)| **down && code.starts_with("Gamepad") && !held.contains(*code))
+            .map(|(code, _)| code.clone())
+            .collect();
+
+        for code in released {
+            self.key_up(code);
+        }
+
+        for code in held {
+            if !self.get_key_down(&code) {
+                self.key_down(code);
+            }
+        }
+
+        self.set_analog("GamepadLeftX", left.x);
+        self.set_analog("GamepadLeftY", left.y);
+        self.set_analog("GamepadRightX", right.x);
+        self.set_analog("GamepadRightY", right.y);
+        self.set_analog("GamepadLeftTrigger", left_trigger);
+        self.set_analog("GamepadRightTrigger", right_trigger);
+    }
+
+    fn apply_virtual_keys(&mut self) {
+        let changed: Vec<(String, bool)> = self
+            .virtual_keys
+            .iter()
+            .filter(|(code, down)| self.get_key_down(code) != **down)
+            .map(|(code, down)| (code.clone(), *down))
+            .collect();
+
+        for (code, down) in changed {
+            if down {
+                self.key_down(code);
+            } else {
+                self.key_up(code);
+            }
+        }
+    }
+
+    /// Records every frame polled from now on into `recording`.
+    /// Gamepads are forgotten so the recording opens with their connection
+    /// and current readings.
+    pub fn start_recording(&mut self, recording: InputRecording) {
+        self.gamepads = Gamepads::new();
+        self.recording = Some(recording);
+    }
+
+    pub fn stop_recording(&mut self) -> Option<InputRecording> {
+        self.recording.take()
+    }
+
+    /// Forgets all key state and plays `recording` back from the next frame.
+    /// Live events are ignored until `stop_replay`.
+    pub fn replay(&mut self, recording: &InputRecording) {
+        self.key_map.clear();
+        self.key_pressed_map.clear();
+        self.key_released_map.clear();
+        self.key_repeated_map.clear();
+        self.text.clear();
+        self.edits.clear();
+        self.pointer = Pointer::new();
+        self.touches = Touches::new();
+        self.gamepads = Gamepads::new();
+        self.virtual_keys.clear();
+        self.analog_values.clear();
+        self.source = Box::new(InputReplay::new(recording));
+        self.replaying = true;
+    }
+
+    /// Goes back to live events, dropping any that queued up meanwhile.
+    pub fn stop_replay(&mut self) {
+        self.queue.clear();
+        self.source = Box::new(self.queue.clone());
+        self.replaying = false;
+        self.gamepads = Gamepads::new();
+    }
+
+    pub fn key_down(&mut self, code: String) {
+        if !self.get_key_down(&code) {
+            if code == "Backspace" {
+                self.edits.push(TextEdit::Delete);
+            }
+            self.key_pressed_map.insert(code.clone(), true);
+        }
+
+        self.key_map.insert(code, true);
+    }
+
+    pub fn key_up(&mut self, code: String) {
+        if self.get_key_down(&code) {
+            self.key_released_map.insert(code.clone(), true);
+        }
+
+        self.key_map.insert(code, false);
+    }
+
+    pub fn flush_pressed_map(&mut self) {
+        self.key_pressed_map.clear();
+        self.key_released_map.clear();
+        self.key_repeated_map.clear();
+        self.text.clear();
+        self.edits.clear();
+        self.pointer.flush();
+        self.touches.flush();
+        self.gamepads.flush();
+    }
+
+    pub fn get_key_down(&self, code: &str) -> bool {
+        match self.key_map.get(code) {
+            None => false,
+            Some(pressed) => *pressed,
+        }
+    }
+
+    pub fn key_was_pressed(&self, code: &str) -> bool {
+        match self.key_pressed_map.get(code) {
+            None => false,
+            Some(pressed) => *pressed,
+        }
+    }
+
+    pub fn key_was_released(&self, code: &str) -> bool {
+        match self.key_released_map.get(code) {
+            None => false,
+            Some(released) => *released,
+        }
+    }
+
+    /// Whether a held key auto-repeated this frame. The first press doesn't
+    /// count.
+    pub fn key_was_repeated(&self, code: &str) -> bool {
+        match self.key_repeated_map.get(code) {
+            None => false,
+            Some(repeated) => *repeated,
+        }
+    }
+
+    /// Whether a key went down or auto-repeated this frame, as for moving
+    /// through a menu or deleting text.
+    pub fn key_was_pressed_or_repeated(&self, code: &str) -> bool {
+        self.key_was_pressed(code) || self.key_was_repeated(code)
+    }
+
+    pub fn modifiers(&self) -> Modifiers {
+        let either = |side: &str| {
+            self.get_key_down(&format!("{side}Left")) || self.get_key_down(&format!("{side}Right"))
+        };
+
+        Modifiers {
+            shift: either("Shift"),
+            control: either("Control"),
+            alt: either("Alt"),
+            meta: either("Meta"),
+        }
+    }
+
+    /// The characters typed this frame, in order.
+    pub fn typed_text(&self) -> &str {
+        &self.text
+    }
+
+    /// Applies this frame's typing to `text` in the order it happened: typed
+    /// characters are appended while it is shorter than `max_chars`, and each
+    /// Backspace press or repeat deletes the last one.
+    pub fn edit_text(&self, text: &mut String, max_chars: usize) {
+        for edit in &self.edits {
+            match *edit {
+                TextEdit::Type(character) => {
+                    if text.chars().count() < max_chars {
+                        text.push(character);
+                    }
+                }
+                TextEdit::Delete => {
+                    text.pop();
+                }
+            }
+        }
+    }
+
+    /// Whether any key bound to `action` is held.
+    pub fn action_down(&self, action: &str) -> bool {
+        self.bindings
+            .action_keys(action)
+            .iter()
+            .any(|code| self.get_key_down(code))
+    }
+
+    /// Whether `action` went down this frame: a key bound to it was pressed
+    /// while none of the others were already held.
+    pub fn action_pressed(&self, action: &str) -> bool {
+        let codes = self.bindings.action_keys(action);
+
+        codes.iter().any(|code| self.key_was_pressed(code))
+            && !codes.iter().any(|code| self.key_held_before_frame(code))
+    }
+
+    /// Whether `action` came up this frame: a key bound to it was released
+    /// and none of the others are still held.
+    pub fn action_released(&self, action: &str) -> bool {
+        self.bindings
+            .action_keys(action)
+            .iter()
+            .any(|code| self.key_was_released(code))
+            && !self.action_down(action)
+    }
+
+    // Whether the key was down when the frame began, worked out from its
+    // edges. A key tapped within the frame counts as having been up.
+    fn key_held_before_frame(&self, code: &str) -> bool {
+        if self.key_was_pressed(code) {
+            return false;
+        }
+
+        self.get_key_down(code) || self.key_was_released(code)
+    }
+
+    /// The value of `axis` in `[-1, 1]`, summed over its key pairs and analog
+    /// sources.
+    pub fn axis_value(&self, axis: &str) -> f64 {
+        let mut value: f64 = 0.0;
+
+        for key_axis in self.bindings.axis_keys(axis) {
+            if self.get_key_down(&key_axis.negative) {
+                value -= 1.0;
+            }
+            if self.get_key_down(&key_axis.positive) {
+                value += 1.0;
+            }
+        }
+
+        for source in self.bindings.analog_sources(axis) {
+            value += self.analog_value(source);
+        }
+
+        value.clamp(-1.0, 1.0)
+    }
+}
+
+impl Default for Input {
+    fn default() -> Self {
+        Self::new()
+    }
+}
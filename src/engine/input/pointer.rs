@@ -0,0 +1,75 @@
+use crate::engine::camera::Camera;
+use crate::engine::vec2f::Vec2f;
+use std::collections::HashMap;
+
+pub const BUTTON_LEFT: u16 = 0;
+pub const BUTTON_MIDDLE: u16 = 1;
+pub const BUTTON_RIGHT: u16 = 2;
+
+/// Mouse or pen state. Positions are in screen coordinates: 1920x1080 with the
+/// origin at the top left and y pointing down, the same space text is drawn
+/// in.
+pub struct Pointer {
+    pub position: Vec2f,
+    pub button_map: HashMap<u16, bool>,
+    pub button_pressed_map: HashMap<u16, bool>,
+    pub button_released_map: HashMap<u16, bool>,
+    pub wheel_delta: f64,
+}
+
+impl Pointer {
+    pub fn new() -> Self {
+        Self {
+            position: [960.0, 540.0].into(),
+            button_map: HashMap::new(),
+            button_pressed_map: HashMap::new(),
+            button_released_map: HashMap::new(),
+            wheel_delta: 0.0,
+        }
+    }
+
+    pub fn button_down(&mut self, button: u16) {
+        if !self.get_button_down(button) {
+            self.button_pressed_map.insert(button, true);
+        }
+
+        self.button_map.insert(button, true);
+    }
+
+    pub fn button_up(&mut self, button: u16) {
+        if self.get_button_down(button) {
+            self.button_released_map.insert(button, true);
+        }
+
+        self.button_map.insert(button, false);
+    }
+
+    pub fn flush(&mut self) {
+        self.button_pressed_map.clear();
+        self.button_released_map.clear();
+        self.wheel_delta = 0.0;
+    }
+
+    pub fn get_button_down(&self, button: u16) -> bool {
+        *self.button_map.get(&button).unwrap_or(&false)
+    }
+
+    pub fn button_was_pressed(&self, button: u16) -> bool {
+        *self.button_pressed_map.get(&button).unwrap_or(&false)
+    }
+
+    pub fn button_was_released(&self, button: u16) -> bool {
+        *self.button_released_map.get(&button).unwrap_or(&false)
+    }
+
+    /// The pointer's position in the world as seen through `camera`.
+    pub fn world_position(&self, camera: &Camera) -> Vec2f {
+        camera.screen_to_world(&self.position)
+    }
+}
+
+impl Default for Pointer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,280 @@
+use crate::engine::input::source::InputSource;
+use crate::engine::input::touch::TouchPhase;
+use crate::engine::input::InputEvent;
+use crate::engine::rng::RngState;
+
+const HEADER: &str = "arcamedia-input 1";
+
+/// The input events of one frame that had any.
+#[derive(Clone, PartialEq, Debug)]
+pub struct RecordedFrame {
+    pub frame: u64,
+    pub delta_time: f64,
+    pub events: Vec<InputEvent>,
+}
+
+/// Every input event applied over a run of frames, along with the RNG state it
+/// started from. Frames without events aren't stored.
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct InputRecording {
+    pub rng: Option<RngState>,
+    pub frame_count: u64,
+    pub frames: Vec<RecordedFrame>,
+}
+
+impl InputRecording {
+    pub fn new(rng: Option<RngState>) -> Self {
+        Self {
+            rng,
+            frame_count: 0,
+            frames: Vec::new(),
+        }
+    }
+
+    pub fn record(&mut self, delta_time: f64, events: &[InputEvent]) {
+        if !events.is_empty() {
+            self.frames.push(RecordedFrame {
+                frame: self.frame_count,
+                delta_time,
+                events: events.to_vec(),
+            });
+        }
+
+        self.frame_count += 1;
+    }
+
+    /// One line per frame with events: the frame number, its delta time and
+    /// each event as a token: `+Code`/`-Code` for keys, `*Code` for key
+    /// repeats, `c<code point>` for typed characters, `m<x>,<y>` for
+    /// pointer moves, `d<button>`/`u<button>` for pointer buttons and
+    /// `w<delta>` for the wheel and `t<phase><id>:<x>,<y>` for touches, the
+    /// phase being one of `s`, `m`, `e` or `c`. Gamepads are `g+<pad>` and
+    /// `g-<pad>` for connecting and disconnecting, `gb<pad>:<button>=<value>`
+    /// and `ga<pad>:<axis>=<value>` for readings.
+    pub fn serialize(&self) -> String {
+        let mut out = format!("{HEADER}\n");
+
+        match self.rng {
+            None => out.push_str("rng -\n"),
+            Some(state) => out.push_str(&format!("rng {}\n", state.0)),
+        }
+
+        out.push_str(&format!("frames {}\n", self.frame_count));
+
+        for frame in &self.frames {
+            out.push_str(&format!("{} {}", frame.frame, frame.delta_time));
+            for event in &frame.events {
+                out.push(' ');
+                out.push_str(&event_token(event));
+            }
+            out.push('\n');
+        }
+
+        out
+    }
+
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let mut lines = text.lines();
+
+        if lines.next() != Some(HEADER) {
+            return Err("Not an input recording".into());
+        }
+
+        let rng = match lines.next().and_then(|line| line.strip_prefix("rng ")) {
+            None => return Err("Missing rng line".into()),
+            Some("-") => None,
+            Some(state) => Some(RngState(state.parse().map_err(|_| "Bad rng state")?)),
+        };
+
+        let frame_count = match lines.next().and_then(|line| line.strip_prefix("frames ")) {
+            None => return Err("Missing frames line".into()),
+            Some(count) => count.parse().map_err(|_| "Bad frame count")?,
+        };
+
+        let mut frames = Vec::new();
+        for line in lines.filter(|line| !line.is_empty()) {
+            let mut fields = line.split(' ');
+
+            let frame = fields
+                .next()
+                .and_then(|field| field.parse().ok())
+                .ok_or_else(|| format!("Bad frame number in `{line}`"))?;
+            let delta_time = fields
+                .next()
+                .and_then(|field| field.parse().ok())
+                .ok_or_else(|| format!("Bad delta time in `{line}`"))?;
+
+            let events = fields
+                .map(parse_event_token)
+                .collect::<Result<Vec<InputEvent>, String>>()?;
+
+            frames.push(RecordedFrame {
+                frame,
+                delta_time,
+                events,
+            });
+        }
+
+        Ok(Self {
+            rng,
+            frame_count,
+            frames,
+        })
+    }
+}
+
+/// Plays an `InputRecording` back, one frame per poll.
+pub struct InputReplay {
+    frames: Vec<RecordedFrame>,
+    next: usize,
+    frame: u64,
+}
+
+impl InputReplay {
+    pub fn new(recording: &InputRecording) -> Self {
+        Self {
+            frames: recording.frames.clone(),
+            next: 0,
+            frame: 0,
+        }
+    }
+}
+
+impl InputSource for InputReplay {
+    fn poll(&mut self) -> Vec<InputEvent> {
+        let mut events = Vec::new();
+
+        while let Some(recorded) = self.frames.get(self.next) {
+            if recorded.frame > self.frame {
+                break;
+            }
+
+            events.extend(recorded.events.iter().cloned());
+            self.next += 1;
+        }
+
+        self.frame += 1;
+        events
+    }
+}
+
+fn event_token(event: &InputEvent) -> String {
+    match event {
+        InputEvent::Key { code, down: true } => format!("+{code}"),
+        InputEvent::Key { code, down: false } => format!("-{code}"),
+        InputEvent::KeyRepeat { code } => format!("*{code}"),
+        InputEvent::Text { character } => format!("c{}", *character as u32),
+        InputEvent::PointerMove { x, y } => format!("m{x},{y}"),
+        InputEvent::PointerButton { button, down: true } => format!("d{button}"),
+        InputEvent::PointerButton {
+            button,
+            down: false,
+        } => format!("u{button}"),
+        InputEvent::Wheel { delta } => format!("w{delta}"),
+        InputEvent::Touch { id, phase, x, y } => {
+            let phase = match phase {
+                TouchPhase::Start => 's',
+                TouchPhase::Move => 'm',
+                TouchPhase::End => 'e',
+                TouchPhase::Cancel => 'c',
+            };
+            format!("t{phase}{id}:{x},{y}")
+        }
+        InputEvent::GamepadConnected { pad } => format!("g+{pad}"),
+        InputEvent::GamepadDisconnected { pad } => format!("g-{pad}"),
+        InputEvent::GamepadButton { pad, button, value } => format!("gb{pad}:{button}={value}"),
+        InputEvent::GamepadAxis { pad, axis, value } => format!("ga{pad}:{axis}={value}"),
+    }
+}
+
+fn parse_event_token(token: &str) -> Result<InputEvent, String> {
+    let bad_token = || format!("Bad input event `{token}`");
+
+    let mut chars = token.chars();
+    let kind = chars.next().ok_or_else(bad_token)?;
+    let rest = chars.as_str();
+
+    match kind {
+        '+' | '-' => Ok(InputEvent::Key {
+            code: rest.to_string(),
+            down: kind == '+',
+        }),
+        '*' => Ok(InputEvent::KeyRepeat {
+            code: rest.to_string(),
+        }),
+        'c' => Ok(InputEvent::Text {
+            character: rest
+                .parse()
+                .ok()
+                .and_then(char::from_u32)
+                .ok_or_else(bad_token)?,
+        }),
+        'm' => {
+            let (x, y) = rest.split_once(',').ok_or_else(bad_token)?;
+            Ok(InputEvent::PointerMove {
+                x: x.parse().map_err(|_| bad_token())?,
+                y: y.parse().map_err(|_| bad_token())?,
+            })
+        }
+        'd' | 'u' => Ok(InputEvent::PointerButton {
+            button: rest.parse().map_err(|_| bad_token())?,
+            down: kind == 'd',
+        }),
+        'w' => Ok(InputEvent::Wheel {
+            delta: rest.parse().map_err(|_| bad_token())?,
+        }),
+        't' => {
+            let mut rest = rest.chars();
+            let phase = match rest.next() {
+                Some('s') => TouchPhase::Start,
+                Some('m') => TouchPhase::Move,
+                Some('e') => TouchPhase::End,
+                Some('c') => TouchPhase::Cancel,
+                _ => return Err(bad_token()),
+            };
+            let (id, position) = rest.as_str().split_once(':').ok_or_else(bad_token)?;
+            let (x, y) = position.split_once(',').ok_or_else(bad_token)?;
+
+            Ok(InputEvent::Touch {
+                id: id.parse().map_err(|_| bad_token())?,
+                phase,
+                x: x.parse().map_err(|_| bad_token())?,
+                y: y.parse().map_err(|_| bad_token())?,
+            })
+        }
+        'g' => {
+            if let Some(pad) = rest.strip_prefix('+') {
+                return Ok(InputEvent::GamepadConnected {
+                    pad: pad.parse().map_err(|_| bad_token())?,
+                });
+            }
+            if let Some(pad) = rest.strip_prefix('-') {
+                return Ok(InputEvent::GamepadDisconnected {
+                    pad: pad.parse().map_err(|_| bad_token())?,
+                });
+            }
+
+            let (kind, rest) = rest.split_at(rest.len().min(1));
+            let (pad, rest) = rest.split_once(':').ok_or_else(bad_token)?;
+            let (index, value) = rest.split_once('=').ok_or_else(bad_token)?;
+            let pad = pad.parse().map_err(|_| bad_token())?;
+            let index = index.parse().map_err(|_| bad_token())?;
+            let value = value.parse().map_err(|_| bad_token())?;
+
+            match kind {
+                "b" => Ok(InputEvent::GamepadButton {
+                    pad,
+                    button: index,
+                    value,
+                }),
+                "a" => Ok(InputEvent::GamepadAxis {
+                    pad,
+                    axis: index,
+                    value,
+                }),
+                _ => Err(bad_token()),
+            }
+        }
+        _ => Err(bad_token()),
+    }
+}
@@ -0,0 +1,35 @@
+use crate::engine::input::InputEvent;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+/// Where `Input` gets its events from, one batch per frame.
+pub trait InputSource {
+    fn poll(&mut self) -> Vec<InputEvent>;
+}
+
+/// Events waiting to be applied, filled by the DOM listeners or by hand.
+/// Clones share the same queue.
+#[derive(Clone, Default)]
+pub struct EventQueue {
+    events: Rc<RefCell<Vec<InputEvent>>>,
+}
+
+impl EventQueue {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn push(&self, event: InputEvent) {
+        self.events.borrow_mut().push(event);
+    }
+
+    pub fn clear(&self) {
+        self.events.borrow_mut().clear();
+    }
+}
+
+impl InputSource for EventQueue {
+    fn poll(&mut self) -> Vec<InputEvent> {
+        self.events.borrow_mut().drain(..).collect()
+    }
+}
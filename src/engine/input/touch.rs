@@ -0,0 +1,158 @@
+use crate::engine::vec2f::Vec2f;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum TouchPhase {
+    Start,
+    Move,
+    End,
+    Cancel,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum SwipeDirection {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+impl SwipeDirection {
+    /// The key code a swipe presses for a frame, so it can be bound to actions
+    /// like any key.
+    pub fn key_code(self) -> &'static str {
+        match self {
+            SwipeDirection::Up => "SwipeUp",
+            SwipeDirection::Down => "SwipeDown",
+            SwipeDirection::Left => "SwipeLeft",
+            SwipeDirection::Right => "SwipeRight",
+        }
+    }
+}
+
+/// A finger on the screen, in screen coordinates.
+#[derive(Clone, Debug)]
+pub struct Touch {
+    pub id: i32,
+    pub start_position: Vec2f,
+    pub position: Vec2f,
+    /// Seconds since the touch started.
+    pub age: f64,
+}
+
+#[derive(Clone, Debug)]
+pub struct Swipe {
+    pub direction: SwipeDirection,
+    pub start: Vec2f,
+    pub end: Vec2f,
+}
+
+/// Tracks every finger currently down, plus what started, ended and swiped
+/// this frame.
+pub struct Touches {
+    pub active: Vec<Touch>,
+    pub started: Vec<i32>,
+    pub ended: Vec<Touch>,
+    pub swipes: Vec<Swipe>,
+    /// Shortest distance, in screen pixels, that counts as a swipe.
+    pub swipe_min_distance: f64,
+    /// Longest time, in seconds, a swipe may take.
+    pub swipe_max_time: f64,
+}
+
+impl Touches {
+    pub fn new() -> Self {
+        Self {
+            active: Vec::new(),
+            started: Vec::new(),
+            ended: Vec::new(),
+            swipes: Vec::new(),
+            swipe_min_distance: 80.0,
+            swipe_max_time: 0.5,
+        }
+    }
+
+    pub fn get(&self, id: i32) -> Option<&Touch> {
+        self.active.iter().find(|touch| touch.id == id)
+    }
+
+    pub fn age(&mut self, delta_time: f64) {
+        for touch in &mut self.active {
+            touch.age += delta_time;
+        }
+    }
+
+    pub fn apply(&mut self, id: i32, phase: TouchPhase, position: Vec2f) {
+        match phase {
+            TouchPhase::Start => {
+                self.active.retain(|touch| touch.id != id);
+                self.active.push(Touch {
+                    id,
+                    start_position: position.clone(),
+                    position,
+                    age: 0.0,
+                });
+                self.started.push(id);
+            }
+            TouchPhase::Move => {
+                if let Some(touch) = self.active.iter_mut().find(|touch| touch.id == id) {
+                    touch.position = position;
+                }
+            }
+            TouchPhase::End | TouchPhase::Cancel => {
+                let index = match self.active.iter().position(|touch| touch.id == id) {
+                    None => return,
+                    Some(index) => index,
+                };
+
+                let mut touch = self.active.remove(index);
+                touch.position = position;
+
+                if phase == TouchPhase::End {
+                    if let Some(swipe) = self.detect_swipe(&touch) {
+                        self.swipes.push(swipe);
+                    }
+                }
+
+                self.ended.push(touch);
+            }
+        }
+    }
+
+    pub fn flush(&mut self) {
+        self.started.clear();
+        self.ended.clear();
+        self.swipes.clear();
+    }
+
+    fn detect_swipe(&self, touch: &Touch) -> Option<Swipe> {
+        let delta = &touch.position - &touch.start_position;
+        if delta.len() < self.swipe_min_distance || touch.age > self.swipe_max_time {
+            return None;
+        }
+
+        // Screen y points down.
+        let direction = if delta.x.abs() > delta.y.abs() {
+            if delta.x > 0.0 {
+                SwipeDirection::Right
+            } else {
+                SwipeDirection::Left
+            }
+        } else if delta.y > 0.0 {
+            SwipeDirection::Down
+        } else {
+            SwipeDirection::Up
+        };
+
+        Some(Swipe {
+            direction,
+            start: touch.start_position.clone(),
+            end: touch.position.clone(),
+        })
+    }
+}
+
+impl Default for Touches {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,125 @@
+use std::ops;
+
+/// A set of collision layers, one per bit. The first five are named by
+/// the constants here; `CollisionLayers::layer` hands out the rest.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct LayerMask(pub u32);
+
+impl LayerMask {
+    pub const NONE: LayerMask = LayerMask(0);
+    pub const ALL: LayerMask = LayerMask(u32::MAX);
+
+    pub const DEFAULT: LayerMask = LayerMask(1 << 0);
+    pub const WALL: LayerMask = LayerMask(1 << 1);
+    pub const PICKUP: LayerMask = LayerMask(1 << 2);
+    pub const PLAYER: LayerMask = LayerMask(1 << 3);
+    pub const UI: LayerMask = LayerMask(1 << 4);
+
+    pub fn intersects(self, other: LayerMask) -> bool {
+        self.0 & other.0 != 0
+    }
+
+    pub fn without(self, other: LayerMask) -> LayerMask {
+        LayerMask(self.0 & !other.0)
+    }
+}
+
+impl ops::BitOr for LayerMask {
+    type Output = LayerMask;
+
+    fn bitor(self, rhs: LayerMask) -> Self::Output {
+        LayerMask(self.0 | rhs.0)
+    }
+}
+
+impl ops::Not for LayerMask {
+    type Output = LayerMask;
+
+    fn not(self) -> Self::Output {
+        LayerMask(!self.0)
+    }
+}
+
+/// Named layers and which of them collide with which. Every layer collides
+/// with every other until told otherwise.
+pub struct CollisionLayers {
+    names: Vec<String>,
+    matrix: [u32; 32],
+}
+
+impl CollisionLayers {
+    pub fn new() -> Self {
+        Self {
+            names: ["default", "wall", "pickup", "player", "ui"]
+                .map(String::from)
+                .into(),
+            matrix: [u32::MAX; 32],
+        }
+    }
+
+    /// The layer called `name`, taking the next free bit if there isn't one.
+    pub fn layer(&mut self, name: &str) -> Result<LayerMask, String> {
+        if let Some(layer) = self.get(name) {
+            return Ok(layer);
+        }
+
+        if self.names.len() == 32 {
+            return Err(format!("No room for layer `{name}`, all 32 are taken"));
+        }
+
+        self.names.push(name.to_string());
+        Ok(LayerMask(1 << (self.names.len() - 1)))
+    }
+
+    pub fn get(&self, name: &str) -> Option<LayerMask> {
+        self.names
+            .iter()
+            .position(|other| other == name)
+            .map(|bit| LayerMask(1 << bit))
+    }
+
+    /// Sets whether every layer in `a` collides with every layer in `b`,
+    /// both ways round.
+    pub fn set_collides(&mut self, a: LayerMask, b: LayerMask, collides: bool) {
+        for bit in 0..32 {
+            if a.0 & (1 << bit) != 0 {
+                self.set_row(bit, b, collides);
+            }
+            if b.0 & (1 << bit) != 0 {
+                self.set_row(bit, a, collides);
+            }
+        }
+    }
+
+    /// The layers that something on `layer` collides with.
+    pub fn collision_mask(&self, layer: LayerMask) -> LayerMask {
+        let mut mask = LayerMask::NONE;
+        for bit in 0..32 {
+            if layer.0 & (1 << bit) != 0 {
+                mask = mask | LayerMask(self.matrix[bit]);
+            }
+        }
+
+        mask
+    }
+
+    /// Whether anything on a layer in `a` collides with anything on one in
+    /// `b`.
+    pub fn collides(&self, a: LayerMask, b: LayerMask) -> bool {
+        self.collision_mask(a).intersects(b)
+    }
+
+    fn set_row(&mut self, bit: usize, layers: LayerMask, collides: bool) {
+        if collides {
+            self.matrix[bit] |= layers.0;
+        } else {
+            self.matrix[bit] &= !layers.0;
+        }
+    }
+}
+
+impl Default for CollisionLayers {
+    fn default() -> Self {
+        Self::new()
+    }
+}
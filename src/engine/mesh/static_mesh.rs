@@ -1,37 +1,19 @@
-use crate::engine::gl_objects::vertex_array::VertexArray;
-use crate::engine::gl_objects::vertex_buffer::VertexBuffer;
 use crate::engine::mesh::Mesh;
-use crate::gl;
-use web_sys::WebGl2RenderingContext;
+use crate::engine::renderer::{Renderer, VertexLayout};
 
+#[derive(Clone)]
 pub struct StaticMesh {
-    vert_count: i32,
-    va: VertexArray,
+    id: i32,
 }
 
 impl Mesh for StaticMesh {
-    fn new(vertices: Vec<f32>) -> Self {
-        let va = VertexArray::new();
-        let vb = VertexBuffer::new(&vertices);
+    fn new(renderer: &mut dyn Renderer, vertices: Vec<f32>) -> Self {
+        let id = renderer.create_mesh(&vertices, VertexLayout::Position);
 
-        va.attach_vertex_buffer(&vb, 0, 2, false, 0, 0);
-
-        let vert_count = (vertices.len() / 2) as i32;
-
-        Self { vert_count, va }
+        Self { id }
     }
 
-    fn draw(&self) {
-        self.va.bind();
-        gl().draw_arrays(WebGl2RenderingContext::TRIANGLES, 0, self.vert_count);
-    }
-}
-
-impl Clone for StaticMesh {
-    fn clone(&self) -> Self {
-        Self {
-            vert_count: self.vert_count,
-            va: self.va.clone(),
-        }
+    fn draw(&self, renderer: &mut dyn Renderer) {
+        renderer.draw_mesh(self.id);
     }
 }
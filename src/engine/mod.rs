@@ -1,75 +1,92 @@
+use crate::engine::clock::ManualClock;
+use crate::engine::context::EngineContext;
 use crate::engine::input::Input;
 use crate::engine::object_manager::ObjectManager;
+use crate::engine::renderer::Renderer;
+use crate::engine::rng::Rng;
 use crate::engine::shader_manager::ShaderManager;
+use crate::engine::sprite_batch::SpriteBatch;
 use crate::engine::text_renderer::TextRenderer;
 use crate::engine::texture_manager::TextureManager;
-use wasm_bindgen::JsCast;
-use web_sys::WebGl2RenderingContext;
+#[cfg(feature = "web")]
+use crate::engine::web::webgl::WebGlRenderer;
 
 #[macro_use]
 pub mod web;
 pub mod camera;
-mod gl_objects;
+pub mod clock;
+pub mod collision;
+pub mod context;
+pub mod game_loop;
 pub mod input;
-mod line_seg;
-mod matrix;
-mod mesh;
+pub mod layer;
+pub mod line_seg;
+pub mod matrix;
+pub mod mesh;
 pub mod object;
 pub mod object_manager;
-mod raycast;
-mod shader;
+pub mod physics;
+pub mod query;
+pub mod raycast;
+pub mod renderer;
+pub mod rng;
+pub mod shader;
 pub mod shader_manager;
-mod text_renderer;
-mod texture;
+pub mod shape;
+pub mod spatial_hash;
+pub mod sprite_batch;
+pub mod tag;
+pub mod text_renderer;
+pub mod texture;
 pub mod texture_manager;
 pub mod timer;
-mod transform;
-mod vec2f;
-mod vec2i;
+pub mod transform;
+pub mod vec2f;
+pub mod vec2i;
 
-static mut GL: Option<Box<WebGl2RenderingContext>> = None;
-pub fn gl() -> &'static WebGl2RenderingContext {
-    unsafe { GL.as_deref().expect("WebGL2 Context not initialized") }
-}
-
-pub fn exit() {
-    web_sys::window()
-        .unwrap()
-        .open_with_url_and_target("https://lncn.ac/rarcade", "_parent")
-        .expect("Should be able to open arcade menu.");
-}
+pub use web::exit;
 
 pub struct Engine {}
 
 impl Engine {
-    pub async fn init() {
-        let document = web_sys::window().unwrap().document().unwrap();
-        let canvas = document.get_element_by_id("canvas").unwrap();
-        let canvas: web_sys::HtmlCanvasElement =
-            canvas.dyn_into::<web_sys::HtmlCanvasElement>().unwrap();
-
-        let gl = canvas
-            .get_context("webgl2")
-            .unwrap()
-            .unwrap()
-            .dyn_into::<WebGl2RenderingContext>()
-            .unwrap();
+    #[cfg(feature = "web")]
+    pub async fn init() -> EngineContext {
+        let gl = web::webgl_context("canvas");
 
-        gl.enable(WebGl2RenderingContext::BLEND);
+        let mut input = Input::new();
+        input.listen("canvas");
 
-        gl.blend_func(
-            WebGl2RenderingContext::SRC_ALPHA,
-            WebGl2RenderingContext::ONE_MINUS_SRC_ALPHA,
-        );
+        let mut renderer = WebGlRenderer::new(gl);
+        let batch = SpriteBatch::new(&mut renderer);
 
-        unsafe {
-            GL = Some(Box::from(gl));
+        EngineContext {
+            renderer: Box::new(renderer),
+            om: ObjectManager::new(),
+            sm: ShaderManager::new(),
+            tm: TextureManager::new(),
+            tr: TextRenderer::new().await,
+            batch,
+            input,
+            clock: ManualClock::new(),
+            rng: Rng::from_time(),
         }
+    }
+
+    /// Creates an engine that never touches the DOM, drawing through
+    /// `renderer` instead of WebGL. Input and text are inert.
+    pub fn init_headless(mut renderer: impl Renderer + 'static) -> EngineContext {
+        let batch = SpriteBatch::new(&mut renderer);
 
-        ShaderManager::init();
-        TextureManager::init();
-        ObjectManager::init();
-        Input::init();
-        TextRenderer::init().await;
+        EngineContext {
+            renderer: Box::new(renderer),
+            om: ObjectManager::new(),
+            sm: ShaderManager::new(),
+            tm: TextureManager::new(),
+            tr: TextRenderer::new_headless(),
+            batch,
+            input: Input::new(),
+            clock: ManualClock::new(),
+            rng: Rng::from_time(),
+        }
     }
 }
@@ -1,15 +1,26 @@
+use crate::engine::collision::Collision;
+use crate::engine::context::EngineContext;
+use crate::engine::layer::LayerMask;
+use crate::engine::object_manager::ObjectId;
+use crate::engine::physics::RigidBody;
 use crate::engine::shader::Shader;
+use crate::engine::shape::{Collider, Shape};
+use crate::engine::tag::Tag;
 use crate::engine::transform::Transform;
 
 pub mod player;
 pub mod rect;
 pub mod snake_game;
 pub mod textured_rect;
+pub mod virtual_joystick;
 
 pub trait Object {
-    fn draw(&self) {}
-    fn init(&mut self) {}
-    fn tick(&mut self, _delta_time: f64) {}
+    fn draw(&self, _ctx: &mut EngineContext) {}
+    fn init(&mut self, _ctx: &mut EngineContext) {}
+    fn tick(&mut self, _ctx: &mut EngineContext, _delta_time: f64) {}
+    /// Called on every live object before each simulation step, ticking or
+    /// not. Objects that blend between steps when drawn note where they were.
+    fn begin_step(&mut self) {}
     fn transform(&self) -> Option<&Transform> {
         None
     }
@@ -17,15 +28,46 @@ pub trait Object {
         None
     }
     fn set_transform(&mut self, _transform: Transform) {}
+    /// Objects with a body are moved by the physics step.
+    fn body(&self) -> Option<&RigidBody> {
+        None
+    }
+    fn body_mut(&mut self) -> Option<&mut RigidBody> {
+        None
+    }
+    /// For objects that draw their own meshes, the shader to bind before
+    /// `draw`. Objects drawing through `ctx.batch` need none.
     fn shader(&self) -> Option<&Shader> {
         None
     }
     fn collides(&self) -> bool {
         false
     }
+    /// What the object collides with and is found by queries as, placed by
+    /// its transform.
+    fn shape(&self) -> &Shape {
+        &Shape::Box
+    }
+    fn collider(&self) -> Option<Collider> {
+        self.transform()
+            .map(|transform| self.shape().collider(transform))
+    }
+    /// The collision layers the object is on. Which layers collide with which
+    /// is set on `ObjectManager::layers`.
+    fn layer(&self) -> LayerMask {
+        LayerMask::DEFAULT
+    }
+    /// Called in the step two colliding objects start to overlap. Both
+    /// objects' `collides` must be true.
+    fn on_collision_enter(&mut self, _ctx: &mut EngineContext, _collision: &Collision) {}
+    /// Called in every later step they still overlap.
+    fn on_collision_stay(&mut self, _ctx: &mut EngineContext, _collision: &Collision) {}
+    /// Called in the step they stop overlapping, or `other` stops colliding
+    /// or is despawned.
+    fn on_collision_exit(&mut self, _ctx: &mut EngineContext, _other: ObjectId) {}
     fn color_mut(&mut self) -> Option<&mut [f32; 4]> {
         None
     }
-    fn tags(&self) -> &Vec<String>;
-    fn tags_mut(&mut self) -> &mut Vec<String>;
+    fn tags(&self) -> &Vec<Tag>;
+    fn tags_mut(&mut self) -> &mut Vec<Tag>;
 }
@@ -1,47 +1,43 @@
+use crate::engine::context::EngineContext;
+use crate::engine::input::pointer::BUTTON_RIGHT;
+use crate::engine::layer::LayerMask;
 use crate::engine::line_seg::LineSeg;
-use crate::engine::raycast::{FilterType, Raycast};
-use crate::engine::shader::Shader;
+use crate::engine::object::rect::Rect;
+use crate::engine::object::textured_rect::TexturedRect;
+use crate::engine::object::{Object, Transform};
+use crate::engine::object_manager::ObjectId;
+use crate::engine::physics::RigidBody;
+use crate::engine::raycast::Raycast;
+use crate::engine::tag::Tag;
 use crate::engine::texture::Texture;
 use crate::engine::vec2f::Vec2f;
-use crate::input::input;
 use crate::object;
-use crate::object::rect::Rect;
-use crate::object::textured_rect::TexturedRect;
-use crate::object::{Object, Transform};
-use crate::object_manager::om;
-use js_sys::Math::{cos, random, sin};
 use std::cell::RefCell;
 use std::f64::consts::PI;
 use std::rc::Rc;
 
-pub struct Player<'a> {
+pub struct Player {
     speed: f64,
-    textured_rect: TexturedRect<'a>,
-    raycast_rect: Rect,
+    body: RigidBody,
+    textured_rect: TexturedRect,
+    // Spawned with the player as its child, so it follows without being
+    // moved by hand. `None` until then.
+    raycast_rect: Option<ObjectId>,
     raycast_angle: f64,
     dummy_rect: Rect,
-    pub tags: Vec<String>,
+    pub tags: Vec<Tag>,
 }
 
-impl<'a> Player<'a> {
-    pub async fn new(
+impl Player {
+    pub fn new(
         position: Vec2f,
         size: Vec2f,
         rotation: f64,
         color: [f32; 4],
-        texture: &'a Texture,
+        texture: Texture,
     ) -> Self {
         let textured_rect =
-            TexturedRect::new(position.clone(), size, rotation, color, texture, false).await;
-
-        let raycast_rect = Rect::new(
-            position.clone(),
-            [5.0, 5.0].into(),
-            PI / 4.0,
-            [0.7, 0.2, 0.4, 1.0],
-            false,
-        )
-        .await;
+            TexturedRect::new(position.clone(), size, rotation, color, texture, false);
 
         let dummy_rect = Rect::new(
             [0.0, 0.0].into(),
@@ -49,128 +45,147 @@ impl<'a> Player<'a> {
             0.0,
             [1.0, 1.0, 1.0, 1.0],
             false,
-        )
-        .await;
+        );
 
         Self {
             speed: 1000.0,
+            body: RigidBody {
+                friction: 0.0,
+                continuous: true,
+                ..RigidBody::dynamic()
+            },
             textured_rect,
-            raycast_rect,
+            raycast_rect: None,
             raycast_angle: 0.0,
             dummy_rect,
             tags: ["player".into()].into(),
         }
     }
-}
-
-impl Object for Player<'_> {
-    fn draw(&self) {
-        self.textured_rect.shader().unwrap().bind();
-        self.textured_rect.draw();
-
-        self.raycast_rect.shader().unwrap().bind();
-        self.raycast_rect.draw();
-    }
 
-    fn tick(&mut self, delta_time: f64) {
-        let mut key_dir = Vec2f::new(0.0, 0.0);
+    /// Spawns the player along with the rect showing where it aims, parented
+    /// to it.
+    pub fn spawn(self, ctx: &mut EngineContext) -> ObjectId {
+        let player = Rc::new(RefCell::new(self));
+        let id = ctx.om.spawn(player.clone());
 
-        if input().get_key_down("KeyW") {
-            key_dir.y += 1.0;
-        }
-        if input().get_key_down("KeyS") {
-            key_dir.y -= 1.0;
-        }
-        if input().get_key_down("KeyA") {
-            key_dir.x -= 1.0;
-        }
-        if input().get_key_down("KeyD") {
-            key_dir.x += 1.0;
-        }
+        // Placed relative to the player once parented.
+        let mut raycast_rect = Rect::new(
+            [0.0, 0.0].into(),
+            [5.0, 5.0].into(),
+            PI / 4.0,
+            [0.7, 0.2, 0.4, 1.0],
+            false,
+        );
+        // Kept out of the player's own raycasts.
+        raycast_rect.layer = LayerMask::PLAYER;
 
-        let speed = self.speed * delta_time;
+        let raycast_rect = object!(ctx, raycast_rect);
+        ctx.om
+            .set_parent(raycast_rect, Some(id))
+            .expect("Both were just spawned.");
+        player.borrow_mut().raycast_rect = Some(raycast_rect);
 
-        let delta_position = &key_dir.normalize() * speed;
+        id
+    }
+}
 
-        let old_position = self.transform().unwrap().position.clone();
-        self.transform_mut().unwrap().position += &delta_position;
+impl Object for Player {
+    fn draw(&self, ctx: &mut EngineContext) {
+        self.textured_rect.draw(ctx);
+    }
 
-        for object in &om().objects_on_screen[1..] {
-            let object = object.borrow();
-            let transform = object.transform().unwrap();
+    fn tick(&mut self, ctx: &mut EngineContext, delta_time: f64) {
+        let key_dir = Vec2f::new(
+            ctx.input.axis_value("move_x"),
+            ctx.input.axis_value("move_y"),
+        );
 
-            if object.collides() && transform.overlaps(self.transform().unwrap()) {
-                self.transform_mut().unwrap().position = old_position;
-                break;
-            }
-        }
+        // The physics step moves the player, sliding it along whatever it
+        // runs into.
+        self.body.velocity = &key_dir.normalize() * self.speed;
 
-        if input().get_key_down("KeyM") {
-            self.raycast_angle -= 2.0 * delta_time;
-        }
+        self.raycast_angle += ctx.input.axis_value("turn") * 2.0 * delta_time;
 
-        if input().get_key_down("KeyN") {
-            self.raycast_angle += 2.0 * delta_time;
+        if ctx.input.pointer.get_button_down(BUTTON_RIGHT) {
+            let to_pointer = &ctx.input.pointer.world_position(&ctx.om.camera)
+                - &self.transform().unwrap().position;
+            self.raycast_angle = to_pointer.y.atan2(to_pointer.x);
         }
 
         let raycast_length = 500.0;
         let mut raycast_p2 = Vec2f::new(
-            raycast_length * cos(self.raycast_angle),
-            raycast_length * sin(self.raycast_angle),
+            raycast_length * self.raycast_angle.cos(),
+            raycast_length * self.raycast_angle.sin(),
         );
 
-        self.raycast_rect.transform_mut().unwrap().position =
-            &self.transform().unwrap().position + &(&raycast_p2 / 2.0);
-        self.raycast_rect.transform_mut().unwrap().rotation = self.raycast_angle;
-        self.raycast_rect.transform_mut().unwrap().size.x = raycast_length;
+        // Aimed relative to the player, which carries it along from there.
+        let local_angle = self.raycast_angle - self.transform().unwrap().rotation;
+        if let Some(local_transform) = self
+            .raycast_rect
+            .and_then(|id| ctx.om.local_transform_mut(id))
+        {
+            local_transform.position = Vec2f::new(
+                raycast_length / 2.0 * local_angle.cos(),
+                raycast_length / 2.0 * local_angle.sin(),
+            );
+            local_transform.rotation = local_angle;
+            local_transform.size.x = raycast_length;
+        }
 
         raycast_p2 += &self.transform().unwrap().position.clone();
 
-        if input().get_key_down("KeyF") {
+        if ctx.input.action_down("fire") {
             let mut rect = self.dummy_rect.clone();
             rect.transform_mut().unwrap().position = raycast_p2.clone();
-            rect.color[0] = random() as f32;
-            rect.color[1] = random() as f32;
-            rect.color[2] = random() as f32;
+            rect.color[0] = ctx.rng.float() as f32;
+            rect.color[1] = ctx.rng.float() as f32;
+            rect.color[2] = ctx.rng.float() as f32;
 
-            object!(rect);
+            let id = object!(ctx, rect);
+            ctx.om.set_tick_enabled(id, false);
         }
 
-        if input().get_key_down("KeyR") {
+        if ctx.input.action_down("spin_target") {
             let ray = LineSeg::new(
                 self.transform().unwrap().position.clone(),
                 raycast_p2.clone(),
             );
 
-            let mut raycast = Raycast::new(ray, ["player".into()].into());
-            raycast.fire(FilterType::Blacklist);
+            let mut raycast = Raycast::new(ray, !LayerMask::PLAYER);
+            raycast.fire(&ctx.om);
 
             match raycast.hit {
                 None => {}
                 Some(hit) => {
-                    hit.object.borrow_mut().transform_mut().unwrap().rotation += 8.0 * delta_time;
+                    if let Some(object) = ctx.om.get(hit.object) {
+                        object.borrow_mut().transform_mut().unwrap().rotation += 8.0 * delta_time;
+                    }
                 }
             }
         }
 
-        if input().get_key_down("KeyG") {
+        if ctx.input.action_down("destroy_target") {
             let ray = LineSeg::new(
                 self.transform().unwrap().position.clone(),
                 raycast_p2.clone(),
             );
 
-            let mut raycast = Raycast::new(ray, ["player".into()].into());
-            raycast.fire(FilterType::Blacklist);
+            let mut raycast = Raycast::new(ray, !LayerMask::PLAYER);
+            raycast.fire(&ctx.om);
 
             match raycast.hit {
                 None => {}
                 Some(hit) => {
-                    om().remove_object(hit.object.clone());
+                    ctx.om.despawn(hit.object);
                 }
             }
         }
     }
 
+    fn begin_step(&mut self) {
+        self.textured_rect.begin_step();
+    }
+
     fn transform(&self) -> Option<&Transform> {
         self.textured_rect.transform()
     }
@@ -183,19 +198,31 @@ impl Object for Player<'_> {
         self.textured_rect.set_transform(transform);
     }
 
-    fn shader(&self) -> Option<&Shader> {
-        self.textured_rect.shader()
+    fn collides(&self) -> bool {
+        true
+    }
+
+    fn layer(&self) -> LayerMask {
+        LayerMask::PLAYER
+    }
+
+    fn body(&self) -> Option<&RigidBody> {
+        Some(&self.body)
+    }
+
+    fn body_mut(&mut self) -> Option<&mut RigidBody> {
+        Some(&mut self.body)
     }
 
     fn color_mut(&mut self) -> Option<&mut [f32; 4]> {
         self.textured_rect.color_mut()
     }
 
-    fn tags(&self) -> &Vec<String> {
+    fn tags(&self) -> &Vec<Tag> {
         &self.tags
     }
 
-    fn tags_mut(&mut self) -> &mut Vec<String> {
+    fn tags_mut(&mut self) -> &mut Vec<Tag> {
         &mut self.tags
     }
 }
@@ -1,56 +1,57 @@
-use crate::engine::mesh::static_mesh::StaticMesh;
-use crate::engine::mesh::Mesh;
-use crate::engine::shader::Shader;
+use crate::engine::context::EngineContext;
+use crate::engine::layer::LayerMask;
+use crate::engine::object::{Object, Transform};
+use crate::engine::shape::Shape;
+use crate::engine::sprite_batch::FULL_UV;
+use crate::engine::tag::Tag;
 use crate::engine::vec2f::Vec2f;
-use crate::object::{Object, Transform};
-use crate::shader_manager::sm;
 
 pub struct Rect {
     transform: Transform,
-    shader: Shader,
-    mesh: StaticMesh,
+    // Where the rect was when the step began, to draw it blended towards
+    // `transform`. `None` until its first step.
+    previous_transform: Option<Transform>,
     collides: bool,
+    pub layer: LayerMask,
+    pub shape: Shape,
     pub color: [f32; 4],
-    pub tags: Vec<String>,
+    pub tags: Vec<Tag>,
 }
 
 impl Rect {
-    pub async fn new(
+    pub fn new(
         position: Vec2f,
         size: Vec2f,
         rotation: f64,
         color: [f32; 4],
         collides: bool,
     ) -> Self {
-        let shader = sm()
-            .get_shader("colored_vert.glsl", "colored_frag.glsl")
-            .await
-            .clone();
-
         let transform = Transform::new(position, size, rotation);
 
-        let mesh = StaticMesh::new(vec![
-            -0.5, -0.5, 0.5, -0.5, -0.5, 0.5, 0.5, -0.5, 0.5, 0.5, -0.5, 0.5,
-        ]);
-
         Self {
             transform,
-            mesh,
-            shader,
+            previous_transform: None,
             color,
             collides,
+            layer: LayerMask::DEFAULT,
+            shape: Shape::Box,
             tags: Vec::new(),
         }
     }
 }
 
 impl Object for Rect {
-    fn draw(&self) {
-        self.shader
-            .uniform4fv_with_f32_array("fragColor", &self.color);
-        self.shader.uniform_transform(&self.transform);
+    fn draw(&self, ctx: &mut EngineContext) {
+        let transform = ctx
+            .om
+            .interpolate(self.previous_transform.as_ref(), &self.transform);
 
-        self.mesh.draw();
+        ctx.batch
+            .draw(ctx.renderer.as_mut(), &transform, self.color, None, FULL_UV);
+    }
+
+    fn begin_step(&mut self) {
+        self.previous_transform = Some(self.transform.clone());
     }
 
     fn transform(&self) -> Option<&Transform> {
@@ -65,23 +66,27 @@ impl Object for Rect {
         self.transform = transform;
     }
 
-    fn shader(&self) -> Option<&Shader> {
-        Some(&self.shader)
-    }
-
     fn collides(&self) -> bool {
         self.collides
     }
 
+    fn layer(&self) -> LayerMask {
+        self.layer
+    }
+
+    fn shape(&self) -> &Shape {
+        &self.shape
+    }
+
     fn color_mut(&mut self) -> Option<&mut [f32; 4]> {
         Some(&mut self.color)
     }
 
-    fn tags(&self) -> &Vec<String> {
+    fn tags(&self) -> &Vec<Tag> {
         &self.tags
     }
 
-    fn tags_mut(&mut self) -> &mut Vec<String> {
+    fn tags_mut(&mut self) -> &mut Vec<Tag> {
         &mut self.tags
     }
 }
@@ -89,11 +94,12 @@ impl Object for Rect {
 impl Clone for Rect {
     fn clone(&self) -> Self {
         Self {
-            shader: self.shader.clone(),
             transform: self.transform().unwrap().clone(),
-            mesh: self.mesh.clone(),
-            color: self.color.clone(),
+            previous_transform: None,
+            color: self.color,
             collides: self.collides,
+            layer: self.layer,
+            shape: self.shape.clone(),
             tags: self.tags.clone(),
         }
     }
@@ -1,49 +1,58 @@
-use crate::engine::{exit, gl};
+use crate::engine::collision::Collision;
+use crate::engine::context::EngineContext;
+use crate::engine::exit;
+use crate::engine::input::pointer::BUTTON_LEFT;
+use crate::engine::layer::LayerMask;
 use crate::engine::object::rect::Rect;
 use crate::engine::object::snake_game::snake::Snake;
+use crate::engine::object::virtual_joystick::VirtualJoystick;
 use crate::engine::object::Object;
-use crate::engine::object_manager::om;
-use crate::engine::text_renderer::tr;
-use crate::engine::texture_manager::tm;
+use crate::engine::object_manager::ObjectId;
+use crate::engine::shape::Shape;
+use crate::engine::tag::Tag;
+use crate::engine::timer::Timer;
 use crate::engine::transform::Transform;
 use crate::engine::vec2f::Vec2f;
 use crate::object;
-use js_sys::Math::random;
 use std::cell::RefCell;
 use std::rc::Rc;
-use web_sys::WebGl2RenderingContext;
-use crate::engine::timer::Timer;
 
 mod segment;
 mod snake;
 
 const TILE_SIZE: f64 = 50.0;
 
-pub struct SnakeGame<'a> {
-    tags: Vec<String>,
+pub struct SnakeGame {
+    tags: Vec<Tag>,
     bounds: Vec2f,
     color: [f32; 4],
     playing: bool,
     dummy_wall: Rect,
     dummy_apple: Rect,
-    snake: Snake<'a>,
+    snake: Snake,
     end_timer: Timer,
     end: bool,
     won: bool,
+    win_text_position: Vec2f,
+    win_color: [f32; 4],
     max_segments: i32,
+    /// Spawned once a touch shows the game is being played on a touch
+    /// screen.
+    joystick: Option<ObjectId>,
 }
 
-impl SnakeGame<'_> {
-    pub async fn new(bounds: Vec2f) -> Self {
+impl SnakeGame {
+    pub async fn new(ctx: &mut EngineContext, bounds: Vec2f) -> Self {
         let mut dummy_apple = Rect::new(
             [0.0, 0.0].into(),
             [TILE_SIZE - 5.0, TILE_SIZE - 5.0].into(),
             0.0,
             [1.0, 0.2, 0.2, 1.0],
             true,
-        )
-        .await;
+        );
         dummy_apple.tags_mut().push("apple".into());
+        dummy_apple.layer = LayerMask::PICKUP;
+        dummy_apple.shape = Shape::Circle { radius: 0.5 };
 
         let mut dummy_wall = Rect::new(
             [0.0, 0.0].into(),
@@ -51,20 +60,21 @@ impl SnakeGame<'_> {
             0.0,
             [1.0, 1.0, 1.0, 1.0],
             true,
-        )
-        .await;
+        );
         dummy_wall.tags_mut().push("wall".into());
+        dummy_wall.layer = LayerMask::WALL;
 
-        let tags: Vec<String> = [String::from("snake game")].into();
+        let tags: Vec<Tag> = ["snake game".into()].into();
 
-        let snake = Snake::new(
-            200.0,
-            [0.0, 0.0].into(),
-            tm().get_texture("snake head.png").await,
-        )
-        .await;
+        let texture = ctx
+            .tm
+            .get_texture(ctx.renderer.as_mut(), "snake head.png")
+            .await
+            .clone();
+        let snake = Snake::new(200.0, [0.0, 0.0].into(), texture);
 
-        let mut max_segments = (((bounds.x-TILE_SIZE) / TILE_SIZE) * ((bounds.y-TILE_SIZE) / TILE_SIZE)) as i32;
+        let mut max_segments =
+            (((bounds.x - TILE_SIZE) / TILE_SIZE) * ((bounds.y - TILE_SIZE) / TILE_SIZE)) as i32;
         max_segments -= 1;
 
         log!("{max_segments}");
@@ -77,14 +87,17 @@ impl SnakeGame<'_> {
             color: [40.0 / 255.0, 220.0 / 255.0, 40.0 / 255.0, 1.0],
             playing: false,
             snake,
-            end_timer: Timer::new(),
+            end_timer: Timer::with_clock(ctx.clock.clone()),
             end: false,
             won: false,
+            win_text_position: [0.0, 0.0].into(),
+            win_color: [0.0, 0.0, 0.0, 1.0],
             max_segments,
+            joystick: None,
         }
     }
 
-    fn add_apple(&mut self) {
+    fn add_apple(&mut self, ctx: &mut EngineContext) {
         let mut possible_spawns: Vec<Vec2f> = Vec::new();
         let mut x = -self.bounds.x / 2.0 + TILE_SIZE;
         let mut y = -self.bounds.y / 2.0 + TILE_SIZE;
@@ -109,70 +122,121 @@ impl SnakeGame<'_> {
             *pos != self.snake.head_rect.transform().unwrap().position
         });
 
-        let pos =
-            possible_spawns[(random() * possible_spawns.len() as f64).round() as usize].clone();
+        let pos = match ctx.rng.choose(&possible_spawns) {
+            None => return,
+            Some(pos) => pos.clone(),
+        };
         let mut apple = self.dummy_apple.clone();
         apple.transform_mut().unwrap().position = pos;
-        object!(apple);
+        object!(ctx, apple);
     }
 
-    fn end_game(&mut self) {
+    fn end_game(&mut self, ctx: &mut EngineContext) {
         let _ = self.end_timer.elapsed_reset();
         self.end = true;
 
         self.playing = false;
         self.snake.kill();
 
-        tr().clear();
-        tr().draw_text("You died!", [960.0, 350.0].into());
-        tr().draw_text(format!("Final score: {}", self.snake.tail.len()).as_str(), [960.0, 400.0].into());
+        ctx.tr.clear();
+        ctx.tr.draw_text("You died!", [960.0, 350.0].into());
+        ctx.tr.draw_text(
+            format!("Final score: {}", self.snake.tail.len()).as_str(),
+            [960.0, 400.0].into(),
+        );
     }
 
     fn win_game(&mut self) {
         self.won = true;
     }
-}
 
-impl Object for SnakeGame<'_> {
-    fn draw(&self) {
-        if self.won {
-            gl().clear_color(random() as f32, random() as f32, random() as f32, 1.0);
+    fn start(&mut self, ctx: &mut EngineContext) {
+        ctx.om.despawn_tag("start".into());
+        ctx.om.despawn_tag("exit".into());
+        ctx.tr.clear();
+        ctx.tr.set_text_color("yellow".into());
+        self.playing = true;
+        self.snake.head_rect.transform_mut().unwrap().position = [0.0, 0.0].into();
+        self.add_apple(ctx);
+    }
+
+    // The snake's head running into something.
+    fn touch(&mut self, ctx: &mut EngineContext, other: ObjectId) {
+        if Self::has_tag(ctx, other, "start") {
+            self.start(ctx);
+        }
 
+        if Self::has_tag(ctx, other, "exit") {
+            exit();
+        }
+
+        if Self::has_tag(ctx, other, "wall") {
+            self.snake.transform_mut().unwrap().position = self.snake.prev_head_position.clone();
+
+            if self.playing {
+                self.end_game(ctx);
+            }
+        }
+
+        if Self::has_tag(ctx, other, "apple") {
+            self.snake.speed += 5.0;
+            self.snake.add_segment();
+            self.add_apple(ctx);
+            ctx.om.despawn(other);
+        }
+    }
+
+    // Whether the object is still around and tagged `tag`.
+    fn has_tag(ctx: &EngineContext, id: ObjectId, tag: &str) -> bool {
+        ctx.om
+            .get(id)
+            .is_some_and(|object| object.borrow().tags().contains(&tag.into()))
+    }
+}
 
-            tr().draw_text("WIN", [random() * 1920.0, random() * 1080.0].into());
+impl Object for SnakeGame {
+    fn draw(&self, ctx: &mut EngineContext) {
+        if self.won {
+            ctx.tr.draw_text("WIN", self.win_text_position.clone());
 
-            gl().clear(WebGl2RenderingContext::COLOR_BUFFER_BIT);
+            // Whatever was queued before is cleared with the rest.
+            ctx.batch.flush(ctx.renderer.as_mut());
+            ctx.renderer.clear(self.win_color);
         }
 
-        self.snake.draw();
+        self.snake.draw(ctx);
     }
 
-    fn init(&mut self) {
+    fn init(&mut self, ctx: &mut EngineContext) {
+        // Only the snake runs into things.
+        let scenery = LayerMask::WALL | LayerMask::PICKUP | LayerMask::UI;
+        ctx.om.layers.set_collides(scenery, scenery, false);
+
         let bounds = &self.bounds;
 
         let mut wall = self.dummy_wall.clone();
         let transform = wall.transform_mut().unwrap();
         transform.position = [0.0, -bounds.y / 2.0].into();
         transform.size = [bounds.x + 10.0, 10.0].into();
-        object!(wall);
+        object!(ctx, wall);
 
         let mut wall = self.dummy_wall.clone();
         let transform = wall.transform_mut().unwrap();
         transform.position = [0.0, bounds.y / 2.0].into();
         transform.size = [bounds.x + 10.0, 10.0].into();
-        object!(wall);
+        object!(ctx, wall);
 
         let mut wall = self.dummy_wall.clone();
         let transform = wall.transform_mut().unwrap();
         transform.position = [-bounds.x / 2.0, 0.0].into();
         transform.size = [10.0, bounds.y + 10.0].into();
-        object!(wall);
+        object!(ctx, wall);
 
         let mut wall = self.dummy_wall.clone();
         let transform = wall.transform_mut().unwrap();
         transform.position = [bounds.x / 2.0, 0.0].into();
         transform.size = [10.0, bounds.y + 10.0].into();
-        object!(wall);
+        object!(ctx, wall);
 
         let button_size = Vec2f::from([bounds.x / 4.0 - 1.0, bounds.y - TILE_SIZE]);
 
@@ -180,34 +244,55 @@ impl Object for SnakeGame<'_> {
         let transform = exit.transform_mut().unwrap();
         transform.position = [-bounds.x / 2.0 + button_size.x / 2.0 + TILE_SIZE / 2.0, 0.0].into();
         transform.size = button_size.clone();
-        tr().draw_text(
+        ctx.tr.draw_text(
             "Exit",
             [transform.position.x + 960.0, transform.position.y + 540.0].into(),
         );
         [1.0, 0.0, 0.0, 1.0].clone_into(exit.color_mut().unwrap());
         exit.tags_mut().push("exit".into());
-        object!(exit);
+        exit.layer = LayerMask::UI;
+        object!(ctx, exit);
 
         let mut start = self.dummy_wall.clone();
         let transform = start.transform_mut().unwrap();
         transform.position = [bounds.x / 2.0 - button_size.x / 2.0 - TILE_SIZE / 2.0, 0.0].into();
         transform.size = button_size;
-        tr().draw_text(
+        ctx.tr.draw_text(
             "Start",
             [transform.position.x + 960.0, transform.position.y + 540.0].into(),
         );
         [0.0, 1.0, 0.0, 1.0].clone_into(start.color_mut().unwrap());
         start.tags_mut().push("start".into());
-        object!(start);
+        start.layer = LayerMask::UI;
+        object!(ctx, start);
     }
 
-    fn tick(&mut self, delta_time: f64) {
+    fn tick(&mut self, ctx: &mut EngineContext, delta_time: f64) {
+        if self.joystick.is_none() && !ctx.input.touches.active.is_empty() {
+            self.joystick = Some(object!(
+                ctx,
+                VirtualJoystick::new([220.0, 860.0].into(), 120.0)
+            ));
+        }
+
         if self.snake.tail.len() as i32 == self.max_segments {
             self.win_game();
         }
 
+        // Rolled here rather than in `draw` so the flash doesn't consume
+        // random numbers at the frame rate.
+        if self.won {
+            self.win_text_position = [ctx.rng.float() * 1920.0, ctx.rng.float() * 1080.0].into();
+            self.win_color = [
+                ctx.rng.float() as f32,
+                ctx.rng.float() as f32,
+                ctx.rng.float() as f32,
+                1.0,
+            ];
+        }
+
         if self.end && self.end_timer.elapsed() / 1000.0 > 5.0 {
-            tr().set_text_color("black".into());
+            ctx.tr.set_text_color("black".into());
 
             self.end = false;
             self.snake.reset();
@@ -216,99 +301,78 @@ impl Object for SnakeGame<'_> {
 
             let mut exit = self.dummy_wall.clone();
             let transform = exit.transform_mut().unwrap();
-            transform.position = [-self.bounds.x / 2.0 + button_size.x / 2.0 + TILE_SIZE / 2.0, 0.0].into();
+            transform.position = [
+                -self.bounds.x / 2.0 + button_size.x / 2.0 + TILE_SIZE / 2.0,
+                0.0,
+            ]
+            .into();
             transform.size = button_size.clone();
-            tr().draw_text(
+            ctx.tr.draw_text(
                 "Exit",
                 [transform.position.x + 960.0, transform.position.y + 540.0].into(),
             );
             [1.0, 0.0, 0.0, 1.0].clone_into(exit.color_mut().unwrap());
             exit.tags_mut().push("exit".into());
-            object!(exit);
+            exit.layer = LayerMask::UI;
+            object!(ctx, exit);
 
             let mut start = self.dummy_wall.clone();
             let transform = start.transform_mut().unwrap();
-            transform.position = [self.bounds.x / 2.0 - button_size.x / 2.0 - TILE_SIZE / 2.0, 0.0].into();
+            transform.position = [
+                self.bounds.x / 2.0 - button_size.x / 2.0 - TILE_SIZE / 2.0,
+                0.0,
+            ]
+            .into();
             transform.size = button_size;
-            tr().draw_text(
+            ctx.tr.draw_text(
                 "Start",
                 [transform.position.x + 960.0, transform.position.y + 540.0].into(),
             );
             [0.0, 1.0, 0.0, 1.0].clone_into(start.color_mut().unwrap());
             start.tags_mut().push("start".into());
-            object!(start);
+            start.layer = LayerMask::UI;
+            object!(ctx, start);
         }
 
         if self.playing {
-            tr().clear();
-            tr().draw_text(
+            ctx.tr.clear();
+            ctx.tr.draw_text(
                 format!("{}", self.snake.tail.len()).as_str(),
                 [960.0, 80.0].into(),
             );
         }
 
-        self.snake.tick(delta_time);
+        self.snake.tick(ctx, delta_time);
 
-        for object_ref in &om().objects_on_screen {
-            let object = match object_ref.try_borrow() {
-                Ok(obj) => obj,
-                Err(_) => {
-                    continue;
+        if ctx.input.pointer.button_was_pressed(BUTTON_LEFT) {
+            if let Some(id) = ctx.om.object_under_pointer(&ctx.input) {
+                if Self::has_tag(ctx, id, "start") {
+                    self.start(ctx);
                 }
-            };
-
-            if object.tags().contains(&("start".into()))
-                && object
-                    .transform()
-                    .unwrap()
-                    .overlaps(self.snake.transform().unwrap())
-            {
-                om().remove_object_tag("start".into());
-                om().remove_object_tag("exit".into());
-                tr().clear();
-                tr().set_text_color("yellow".into());
-                self.playing = true;
-                self.snake.head_rect.transform_mut().unwrap().position = [0.0, 0.0].into();
-                self.add_apple();
-            }
-
-            if object.tags().contains(&("exit".into()))
-                && object
-                    .transform()
-                    .unwrap()
-                    .overlaps(self.snake.transform().unwrap())
-            {
-                exit();
-            }
 
-            if object.tags().contains(&("wall".into()))
-                && object
-                    .transform()
-                    .unwrap()
-                    .overlaps(self.snake.transform().unwrap())
-            {
-                self.snake.transform_mut().unwrap().position =
-                    self.snake.prev_head_position.clone();
-
-                if self.playing {
-                    self.end_game();
+                if Self::has_tag(ctx, id, "exit") {
+                    exit();
                 }
             }
-
-            if object.tags().contains(&("apple".into()))
-                && object
-                    .transform()
-                    .unwrap()
-                    .overlaps(self.snake.transform().unwrap())
-            {
-                self.snake.speed += 5.0;
-                self.snake.add_segment();
-                self.add_apple();
-                om().remove_object(object_ref.clone());
-            }
         }
     }
 
+    fn on_collision_enter(&mut self, ctx: &mut EngineContext, collision: &Collision) {
+        self.touch(ctx, collision.other);
+    }
+
+    fn on_collision_stay(&mut self, ctx: &mut EngineContext, collision: &Collision) {
+        self.touch(ctx, collision.other);
+    }
+
+    fn collides(&self) -> bool {
+        true
+    }
+
+    fn layer(&self) -> LayerMask {
+        LayerMask::PLAYER
+    }
+
     fn transform(&self) -> Option<&Transform> {
         self.snake.transform()
     }
@@ -317,11 +381,11 @@ impl Object for SnakeGame<'_> {
         Some(&mut self.color)
     }
 
-    fn tags(&self) -> &Vec<String> {
+    fn tags(&self) -> &Vec<Tag> {
         &self.tags
     }
 
-    fn tags_mut(&mut self) -> &mut Vec<String> {
+    fn tags_mut(&mut self) -> &mut Vec<Tag> {
         &mut self.tags
     }
 }
@@ -1,30 +1,30 @@
+use crate::engine::context::EngineContext;
 use crate::engine::object::rect::Rect;
 use crate::engine::object::snake_game::TILE_SIZE;
 use crate::engine::object::Object;
-use crate::engine::shader::Shader;
+use crate::engine::tag::Tag;
 use crate::engine::transform::Transform;
 use crate::engine::vec2f::Vec2f;
 
 #[derive(Clone)]
 pub struct Segment {
-    tags: Vec<String>,
+    tags: Vec<Tag>,
     pub rect: Rect,
     prev_position: Vec2f,
 }
 
 impl Segment {
-    pub async fn new() -> Self {
+    pub fn new() -> Self {
         let rect = Rect::new(
             [0.0, 0.0].into(),
             [TILE_SIZE - 5.0, TILE_SIZE - 5.0].into(),
             0.0,
             [40.0 / 255.0, 220.0 / 255.0, 40.0 / 255.0, 1.0],
             true,
-        )
-        .await;
+        );
 
         Self {
-            tags: [String::from("segment")].into(),
+            tags: ["segment".into()].into(),
             rect,
             prev_position: [0.0, 0.0].into(),
         }
@@ -40,9 +40,19 @@ impl Segment {
     }
 }
 
+impl Default for Segment {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl Object for Segment {
-    fn draw(&self) {
-        self.rect.draw();
+    fn draw(&self, ctx: &mut EngineContext) {
+        self.rect.draw(ctx);
+    }
+
+    fn begin_step(&mut self) {
+        self.rect.begin_step();
     }
 
     fn transform(&self) -> Option<&Transform> {
@@ -57,19 +67,15 @@ impl Object for Segment {
         self.rect.set_transform(transform);
     }
 
-    fn shader(&self) -> Option<&Shader> {
-        self.rect.shader()
-    }
-
     fn color_mut(&mut self) -> Option<&mut [f32; 4]> {
         self.rect.color_mut()
     }
 
-    fn tags(&self) -> &Vec<String> {
+    fn tags(&self) -> &Vec<Tag> {
         &self.tags
     }
 
-    fn tags_mut(&mut self) -> &mut Vec<String> {
+    fn tags_mut(&mut self) -> &mut Vec<Tag> {
         &mut self.tags
     }
 }
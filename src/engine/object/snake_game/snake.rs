@@ -1,12 +1,11 @@
 use super::*;
-use crate::engine::input::input;
+use crate::engine::context::EngineContext;
 use crate::engine::object::snake_game::segment::Segment;
 use crate::engine::object::snake_game::snake::Direction::*;
 use crate::engine::object::textured_rect::TexturedRect;
 use crate::engine::object::Object;
-use crate::engine::shader::Shader;
+use crate::engine::tag::Tag;
 use crate::engine::texture::Texture;
-use crate::engine::timer::Timer;
 use crate::engine::transform::Transform;
 use crate::engine::vec2f::Vec2f;
 use std::cmp::PartialEq;
@@ -21,11 +20,23 @@ enum Direction {
     Right,
 }
 
-pub struct Snake<'a> {
+impl Direction {
+    fn opposite(self) -> Self {
+        match self {
+            Still => Still,
+            Up => Down,
+            Down => Up,
+            Left => Right,
+            Right => Left,
+        }
+    }
+}
+
+pub struct Snake {
     pub speed: f64,
     start_speed: f64,
-    pub head_rect: TexturedRect<'a>,
-    movement_timer: Timer,
+    pub head_rect: TexturedRect,
+    movement_time: f64,
     movement_carry: f64,
     direction: Direction,
     movement_queue: Vec<Direction>,
@@ -33,12 +44,12 @@ pub struct Snake<'a> {
     pub prev_head_position: Vec2f,
     dummy_segment: Segment,
     pub tail: Vec<Segment>,
-    tags: Vec<String>,
+    tags: Vec<Tag>,
     color: [f32; 4],
 }
 
-impl<'a> Snake<'a> {
-    pub async fn new(speed: f64, position: Vec2f, texture: &'a Texture) -> Self {
+impl Snake {
+    pub fn new(speed: f64, position: Vec2f, texture: Texture) -> Self {
         let head_rect = TexturedRect::new(
             position.clone(),
             [TILE_SIZE, TILE_SIZE].into(),
@@ -46,22 +57,19 @@ impl<'a> Snake<'a> {
             [1.0, 1.0, 1.0, 1.0],
             texture,
             false,
-        )
-        .await;
-
-        let movement_timer = Timer::new();
+        );
 
-        let dummy_segment = Segment::new().await;
+        let dummy_segment = Segment::new();
 
         let tail: Vec<Segment> = Vec::new();
-        let tags: Vec<String> = Vec::new();
+        let tags: Vec<Tag> = Vec::new();
         let movement_queue: Vec<Direction> = Vec::new();
 
         Self {
             speed,
             start_speed: speed,
             head_rect,
-            movement_timer,
+            movement_time: 0.0,
             movement_carry: 0.0,
             direction: Still,
             movement_queue,
@@ -105,59 +113,58 @@ impl<'a> Snake<'a> {
         self.tail.clear();
         self.transform_mut().unwrap().position = [0.0, 0.0].into();
         self.speed = self.start_speed;
-        self.movement_timer.elapsed_reset();
+        self.movement_time = 0.0;
+        self.movement_carry = 0.0;
         self.direction = Still;
     }
 }
 
-impl Object for Snake<'_> {
-    fn draw(&self) {
-        match self.tail.first() {
-            None => {}
-            Some(rect) => {
-                rect.shader().unwrap().bind();
-            }
-        }
-
+impl Object for Snake {
+    fn draw(&self, ctx: &mut EngineContext) {
         for rect in &self.tail {
-            rect.draw();
+            rect.draw(ctx);
         }
 
-        self.head_rect.shader().unwrap().bind();
-        self.head_rect.draw();
+        self.head_rect.draw(ctx);
     }
 
-    fn tick(&mut self, _delta_time: f64) {
+    fn begin_step(&mut self) {
+        self.head_rect.begin_step();
+
+        for segment in &mut self.tail {
+            segment.begin_step();
+        }
+    }
+
+    fn tick(&mut self, ctx: &mut EngineContext, delta_time: f64) {
         if self.dead {
             return;
         }
 
-        if input().key_was_pressed("KeyS") {
+        if ctx.input.action_pressed("grow") {
             self.add_segment();
         }
 
-        let distance_to_move =
-            (self.movement_timer.elapsed() / 1000.0) * self.speed + self.movement_carry;
+        self.movement_time += delta_time;
+        let distance_to_move = self.movement_time * self.speed + self.movement_carry;
+
+        for (action, direction) in [("up", Up), ("down", Down), ("left", Left), ("right", Right)] {
+            if !ctx.input.action_pressed(action) {
+                continue;
+            }
 
-        for key in input().key_pressed_map.keys() {
             let last_move = match self.movement_queue.last() {
                 None => self.direction,
                 Some(dir) => *dir,
             };
 
-            if key == "ArrowUp" && last_move != Down {
-                self.movement_queue.push(Up);
-            } else if key == "ArrowDown" && last_move != Up {
-                self.movement_queue.push(Down);
-            } else if key == "ArrowLeft" && last_move != Right {
-                self.movement_queue.push(Left);
-            } else if key == "ArrowRight" && last_move != Left {
-                self.movement_queue.push(Right);
+            if direction != last_move.opposite() {
+                self.movement_queue.push(direction);
             }
         }
-        
+
         if distance_to_move >= TILE_SIZE {
-            self.movement_timer.elapsed_reset();
+            self.movement_time = 0.0;
             self.movement_carry = distance_to_move - TILE_SIZE;
             self.prev_head_position = self.transform().unwrap().position.clone();
 
@@ -234,19 +241,15 @@ impl Object for Snake<'_> {
         self.head_rect.set_transform(transform)
     }
 
-    fn shader(&self) -> Option<&Shader> {
-        self.head_rect.shader()
-    }
-
     fn color_mut(&mut self) -> Option<&mut [f32; 4]> {
         Some(&mut self.color)
     }
 
-    fn tags(&self) -> &Vec<String> {
+    fn tags(&self) -> &Vec<Tag> {
         &self.tags
     }
 
-    fn tags_mut(&mut self) -> &mut Vec<String> {
+    fn tags_mut(&mut self) -> &mut Vec<Tag> {
         &mut self.tags
     }
 }
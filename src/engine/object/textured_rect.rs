@@ -1,68 +1,69 @@
-use crate::engine::mesh::static_mesh_t::StaticMeshT;
-use crate::engine::mesh::Mesh;
-use crate::engine::shader::Shader;
+use crate::engine::context::EngineContext;
+use crate::engine::layer::LayerMask;
+use crate::engine::object::{Object, Transform};
+use crate::engine::shape::Shape;
+use crate::engine::sprite_batch::FULL_UV;
+use crate::engine::tag::Tag;
 use crate::engine::texture::Texture;
 use crate::engine::vec2f::Vec2f;
-use crate::object::{Object, Transform};
-use crate::shader_manager::sm;
 
-pub struct TexturedRect<'a> {
+pub struct TexturedRect {
     transform: Transform,
-    mesh: StaticMeshT,
-    shader: Shader,
-    texture: &'a Texture,
+    // Where the rect was when the step began, to draw it blended towards
+    // `transform`. `None` until its first step.
+    previous_transform: Option<Transform>,
+    texture: Texture,
     collides: bool,
+    pub layer: LayerMask,
+    pub shape: Shape,
     pub color: [f32; 4],
-    pub tags: Vec<String>,
+    /// The part of the texture shown, as `[u0, v0, u1, v1]`.
+    pub uv: [f32; 4],
+    pub tags: Vec<Tag>,
 }
 
-impl<'a> TexturedRect<'a> {
-    pub async fn new(
+impl TexturedRect {
+    pub fn new(
         position: Vec2f,
         size: Vec2f,
         rotation: f64,
         color: [f32; 4],
-        texture: &'a Texture,
+        texture: Texture,
         collides: bool,
     ) -> Self {
-        let shader = sm()
-            .get_shader("textured_vert.glsl", "textured_frag.glsl")
-            .await
-            .clone();
-
         let transform = Transform::new(position, size, rotation);
 
-        #[rustfmt::skip]
-        let mesh = StaticMeshT::new(vec![
-            -0.5, -0.5,  0.0, 0.0,
-             0.5, -0.5,  1.0, 0.0,
-            -0.5,  0.5,  0.0, 1.0,
-             0.5, -0.5,  1.0, 0.0,
-             0.5,  0.5,  1.0, 1.0,
-            -0.5,  0.5,  0.0, 1.0,
-        ]);
-
         Self {
             transform,
-            mesh,
-            shader,
+            previous_transform: None,
             color,
+            uv: FULL_UV,
             texture,
             collides,
+            layer: LayerMask::DEFAULT,
+            shape: Shape::Box,
             tags: Vec::new(),
         }
     }
 }
 
-impl<'a> Object for TexturedRect<'a> {
-    fn draw(&self) {
-        self.texture.bind();
-        self.shader
-            .uniform4fv_with_f32_array("fragColor", &self.color);
-        self.shader.uniform1i("image", 0);
-        self.shader.uniform_transform(&self.transform);
+impl Object for TexturedRect {
+    fn draw(&self, ctx: &mut EngineContext) {
+        let transform = ctx
+            .om
+            .interpolate(self.previous_transform.as_ref(), &self.transform);
 
-        self.mesh.draw();
+        ctx.batch.draw(
+            ctx.renderer.as_mut(),
+            &transform,
+            self.color,
+            Some(&self.texture),
+            self.uv,
+        );
+    }
+
+    fn begin_step(&mut self) {
+        self.previous_transform = Some(self.transform.clone());
     }
 
     fn transform(&self) -> Option<&Transform> {
@@ -77,23 +78,27 @@ impl<'a> Object for TexturedRect<'a> {
         self.transform = transform;
     }
 
-    fn shader(&self) -> Option<&Shader> {
-        Some(&self.shader)
-    }
-
     fn collides(&self) -> bool {
         self.collides
     }
 
+    fn layer(&self) -> LayerMask {
+        self.layer
+    }
+
+    fn shape(&self) -> &Shape {
+        &self.shape
+    }
+
     fn color_mut(&mut self) -> Option<&mut [f32; 4]> {
         Some(&mut self.color)
     }
 
-    fn tags(&self) -> &Vec<String> {
+    fn tags(&self) -> &Vec<Tag> {
         &self.tags
     }
 
-    fn tags_mut(&mut self) -> &mut Vec<String> {
+    fn tags_mut(&mut self) -> &mut Vec<Tag> {
         &mut self.tags
     }
 }
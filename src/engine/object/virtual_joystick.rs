@@ -0,0 +1,144 @@
+use crate::engine::camera::Camera;
+use crate::engine::context::EngineContext;
+use crate::engine::object::rect::Rect;
+use crate::engine::object::{Object, Transform};
+use crate::engine::tag::Tag;
+use crate::engine::vec2f::Vec2f;
+
+/// An on-screen stick for touch screens, pinned to a point on the screen
+/// however the camera moves. A touch that starts on the base drags the knob;
+/// the knob's offset is published as the `JoystickX` and `JoystickY` analog
+/// sources, and pushing it past halfway holds the `JoystickUp`/`Down`/
+/// `Left`/`Right` virtual keys, so it drives the same actions and axes as the
+/// keyboard.
+pub struct VirtualJoystick {
+    base: Rect,
+    knob: Rect,
+    /// Where the stick is centered, in screen coordinates.
+    pub screen_position: Vec2f,
+    /// In screen pixels.
+    radius: f64,
+    touch_id: Option<i32>,
+    pub value: Vec2f,
+    tags: Vec<Tag>,
+}
+
+impl VirtualJoystick {
+    /// A stick centered on `screen_position`, given in screen coordinates
+    /// like touches are, with a base `radius` screen pixels across.
+    pub fn new(screen_position: Vec2f, radius: f64) -> Self {
+        let base = Rect::new(
+            [0.0, 0.0].into(),
+            [radius * 2.0, radius * 2.0].into(),
+            0.0,
+            [1.0, 1.0, 1.0, 0.2],
+            false,
+        );
+
+        let knob = Rect::new(
+            [0.0, 0.0].into(),
+            [radius * 0.8, radius * 0.8].into(),
+            0.0,
+            [1.0, 1.0, 1.0, 0.5],
+            false,
+        );
+
+        Self {
+            base,
+            knob,
+            screen_position,
+            radius,
+            touch_id: None,
+            value: [0.0, 0.0].into(),
+            tags: ["joystick".into()].into(),
+        }
+    }
+
+    // Puts the base under `screen_position` and the knob `offset` screen
+    // pixels from it, as seen through `camera`.
+    fn place(&mut self, camera: &Camera, offset: &Vec2f) {
+        let base = self.base.transform_mut().unwrap();
+        base.position = camera.screen_to_world(&self.screen_position);
+        base.size = [
+            self.radius * 2.0 / camera.zoom,
+            self.radius * 2.0 / camera.zoom,
+        ]
+        .into();
+
+        let knob = self.knob.transform_mut().unwrap();
+        knob.position = camera.screen_to_world(&(&self.screen_position + offset));
+        knob.size = [
+            self.radius * 0.8 / camera.zoom,
+            self.radius * 0.8 / camera.zoom,
+        ]
+        .into();
+    }
+}
+
+impl Object for VirtualJoystick {
+    fn draw(&self, ctx: &mut EngineContext) {
+        self.base.draw(ctx);
+        self.knob.draw(ctx);
+    }
+
+    fn init(&mut self, ctx: &mut EngineContext) {
+        self.place(&ctx.om.camera, &Vec2f::new(0.0, 0.0));
+    }
+
+    fn tick(&mut self, ctx: &mut EngineContext, _delta_time: f64) {
+        if self.touch_id.is_none() {
+            self.touch_id = ctx.input.touches.started.iter().copied().find(|id| {
+                ctx.input.touches.get(*id).is_some_and(|touch| {
+                    (&touch.position - &self.screen_position).len() <= self.radius
+                })
+            });
+        }
+
+        let touch = self.touch_id.and_then(|id| ctx.input.touches.get(id));
+        let mut offset = match touch {
+            None => {
+                self.touch_id = None;
+                Vec2f::new(0.0, 0.0)
+            }
+            Some(touch) => &touch.position - &self.screen_position,
+        };
+
+        if offset.len() > self.radius {
+            offset = &offset.normalize() * self.radius;
+        }
+
+        // Screen y points down.
+        self.value = Vec2f::new(offset.x / self.radius, -offset.y / self.radius);
+        self.place(&ctx.om.camera, &offset);
+
+        ctx.input.set_analog("JoystickX", self.value.x);
+        ctx.input.set_analog("JoystickY", self.value.y);
+        ctx.input.set_virtual_key("JoystickUp", self.value.y > 0.5);
+        ctx.input
+            .set_virtual_key("JoystickDown", self.value.y < -0.5);
+        ctx.input
+            .set_virtual_key("JoystickLeft", self.value.x < -0.5);
+        ctx.input
+            .set_virtual_key("JoystickRight", self.value.x > 0.5);
+    }
+
+    fn transform(&self) -> Option<&Transform> {
+        self.base.transform()
+    }
+
+    fn transform_mut(&mut self) -> Option<&mut Transform> {
+        self.base.transform_mut()
+    }
+
+    fn set_transform(&mut self, transform: Transform) {
+        self.base.set_transform(transform);
+    }
+
+    fn tags(&self) -> &Vec<Tag> {
+        &self.tags
+    }
+
+    fn tags_mut(&mut self) -> &mut Vec<Tag> {
+        &mut self.tags
+    }
+}
@@ -1,110 +1,869 @@
 use crate::engine::camera::Camera;
+use crate::engine::collision::{Collision, Contacts, Manifold};
+use crate::engine::context::EngineContext;
+use crate::engine::input::Input;
+use crate::engine::layer::{CollisionLayers, LayerMask};
 use crate::engine::object::Object;
-use crate::engine::shader_manager::sm;
+use crate::engine::physics::{BodyKind, Physics};
+use crate::engine::query::{QueryFilter, QueryHit};
+use crate::engine::shape::{Collider, Shape};
+use crate::engine::spatial_hash::SpatialHash;
+use crate::engine::tag::Tag;
 use crate::engine::transform::Transform;
+use crate::engine::vec2f::Vec2f;
+use image::ImageFormat;
 use std::cell::RefCell;
+use std::cmp::Reverse;
+use std::collections::HashSet;
+use std::io::Cursor;
 use std::rc::Rc;
 
+/// How far casts look at most, so an endless one still queries a finite box.
+const MAX_QUERY_DISTANCE: f64 = 1e9;
+
 #[macro_export]
 macro_rules! object {
-    ($object: expr) => {
-        om().add_object(Rc::new(RefCell::new($object)));
-        om().objects.last().unwrap().borrow_mut().init()
+    ($ctx: expr, $object: expr) => {
+        $ctx.om.spawn(Rc::new(RefCell::new($object)))
     };
 }
 
-static mut OM: Option<Box<ObjectManager>> = None;
+/// Handle to an object in the `ObjectManager`. The generation is bumped every
+/// time a slot is reused, so a handle to a removed object never resolves to
+/// whatever took its place.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub struct ObjectId {
+    index: u32,
+    generation: u32,
+}
 
-pub fn om() -> &'static mut ObjectManager {
-    unsafe {
-        OM.as_deref_mut()
-            .expect("Object manager should be initialized")
-    }
+struct Slot {
+    generation: u32,
+    object: Option<Rc<RefCell<dyn Object>>>,
+    spawn_order: u64,
+    tick_enabled: bool,
+    tick_priority: i32,
+    parent: Option<ObjectId>,
+    children: Vec<ObjectId>,
+    local_transform: Option<Transform>,
+    scale: Vec2f,
 }
 
 pub struct ObjectManager {
-    pub objects: Vec<Rc<RefCell<dyn Object>>>,
-    pub objects_on_screen: Vec<Rc<RefCell<dyn Object>>>,
+    slots: Vec<Slot>,
+    free_slots: Vec<u32>,
+    pending_spawns: Vec<(ObjectId, Rc<RefCell<dyn Object>>)>,
+    pending_despawns: Vec<ObjectId>,
+    next_spawn_order: u64,
+    tick_order: Vec<ObjectId>,
+    tick_order_dirty: bool,
+    pub objects_on_screen: Vec<ObjectId>,
+    /// How far the frame being drawn is between the last simulation step and
+    /// the next, for objects that interpolate what they draw. 1, drawing the
+    /// latest step as it is, until a `GameLoop` renders.
+    pub interpolation_alpha: f64,
     pub camera: Camera,
+    pub physics: Physics,
+    pub layers: CollisionLayers,
     screen_transform: Transform,
+    index: SpatialHash,
+    /// Objects handed out by `get` since the last reindex, which may have
+    /// been moved behind the index's back.
+    moved: RefCell<HashSet<ObjectId>>,
+    contacts: Contacts,
+    /// What continuous bodies ran into this step. They stop just short of
+    /// it, so these are contacts the overlap test can't see.
+    swept_contacts: Vec<(ObjectId, ObjectId, Manifold)>,
 }
 
 impl ObjectManager {
-    pub fn init() {
-        let objects: Vec<Rc<RefCell<dyn Object>>> = Vec::new();
-        let objects_on_screen: Vec<Rc<RefCell<dyn Object>>> = Vec::new();
+    pub fn new() -> Self {
+        let objects_on_screen: Vec<ObjectId> = Vec::new();
         let camera = Camera::new([0.0, 0.0].into(), 1.0);
 
         let screen_transform: Transform =
             Transform::new(camera.position.clone(), [2120.0, 1280.0].into(), 0.0); // 200px buffer around edge of screen
 
-        unsafe {
-            OM = Some(Box::new(Self {
-                objects,
-                objects_on_screen,
-                camera,
-                screen_transform,
-            }))
+        Self {
+            slots: Vec::new(),
+            free_slots: Vec::new(),
+            pending_spawns: Vec::new(),
+            pending_despawns: Vec::new(),
+            next_spawn_order: 0,
+            tick_order: Vec::new(),
+            tick_order_dirty: false,
+            objects_on_screen,
+            interpolation_alpha: 1.0,
+            camera,
+            physics: Physics::new(),
+            layers: CollisionLayers::new(),
+            screen_transform,
+            index: SpatialHash::new(128.0),
+            moved: RefCell::new(HashSet::new()),
+            contacts: Contacts::new(),
+            swept_contacts: Vec::new(),
         }
     }
 
-    pub fn add_object(&mut self, object: Rc<RefCell<dyn Object>>) -> Rc<RefCell<dyn Object>> {
-        self.objects.push(object);
-        self.objects.last().expect("Will be an object.").clone()
+    /// Queues `object` to be added by the next `apply_pending`, which is also
+    /// when its `init` runs. The returned id is reserved straight away but
+    /// only resolves once the object is live.
+    pub fn spawn(&mut self, object: Rc<RefCell<dyn Object>>) -> ObjectId {
+        let id = match self.free_slots.pop() {
+            Some(index) => ObjectId {
+                index,
+                generation: self.slots[index as usize].generation,
+            },
+            None => {
+                self.slots.push(Slot {
+                    generation: 0,
+                    object: None,
+                    spawn_order: 0,
+                    tick_enabled: true,
+                    tick_priority: 0,
+                    parent: None,
+                    children: Vec::new(),
+                    local_transform: None,
+                    scale: [1.0, 1.0].into(),
+                });
+
+                ObjectId {
+                    index: self.slots.len() as u32 - 1,
+                    generation: 0,
+                }
+            }
+        };
+
+        let slot = &mut self.slots[id.index as usize];
+        slot.spawn_order = self.next_spawn_order;
+        slot.tick_enabled = true;
+        slot.tick_priority = 0;
+        slot.parent = None;
+        slot.children.clear();
+        slot.local_transform = None;
+        slot.scale = [1.0, 1.0].into();
+        self.next_spawn_order += 1;
+
+        self.pending_spawns.push((id, object));
+        id
     }
 
-    pub fn remove_object(&mut self, object: Rc<RefCell<dyn Object>>) {
-        for i in 1..self.objects.len() {
-            if Rc::ptr_eq(&self.objects[i], &object) {
-                self.objects.remove(i);
+    /// Queues the object behind `id`, and everything parented to it, to be
+    /// removed by the next `apply_pending`. Stale handles are ignored. The
+    /// root object (the first one spawned) stays for good: despawning it is
+    /// a bug, caught in debug builds.
+    pub fn despawn(&mut self, id: ObjectId) {
+        debug_assert!(id.index != 0, "The root object can't be despawned");
+        self.pending_despawns.push(id);
+    }
+
+    pub fn despawn_tag(&mut self, tag: Tag) {
+        let tagged: Vec<ObjectId> = self
+            .live_objects()
+            .filter(|(_, object)| match object.try_borrow() {
+                Ok(object) => object.tags().contains(&tag),
+                Err(_) => false,
+            })
+            .map(|(id, _)| id)
+            .collect();
+
+        self.pending_despawns.extend(tagged);
+    }
+
+    /// Carries out queued despawns and then queued spawns, initializing each
+    /// new object. Anything queued while doing so is applied too, so nothing
+    /// is left pending on return. Called at the end of every update step.
+    pub fn apply_pending(ctx: &mut EngineContext) {
+        loop {
+            let despawns = std::mem::take(&mut ctx.om.pending_despawns);
+            let spawns = std::mem::take(&mut ctx.om.pending_spawns);
+
+            if despawns.is_empty() && spawns.is_empty() {
                 return;
             }
+
+            for id in despawns {
+                ctx.om.remove_object(id);
+            }
+
+            for (id, object) in spawns {
+                // Despawned before it ever went live.
+                if ctx.om.slots[id.index as usize].generation != id.generation {
+                    continue;
+                }
+
+                ctx.om.slots[id.index as usize].object = Some(object.clone());
+                ctx.om.tick_order_dirty = true;
+                object.borrow_mut().init(ctx);
+                ctx.om.reindex(id);
+            }
+
+            ctx.om.reindex_moved();
+        }
+    }
+
+    fn remove_object(&mut self, id: ObjectId) {
+        if id.index == 0 {
+            return;
+        }
+
+        let slot = &mut self.slots[id.index as usize];
+        if slot.generation != id.generation {
+            return;
+        }
+
+        slot.generation += 1;
+        slot.object = None;
+        self.index.remove(id);
+        self.moved.borrow_mut().remove(&id);
+        let parent = slot.parent.take();
+        let children = std::mem::take(&mut slot.children);
+        self.free_slots.push(id.index);
+        self.tick_order_dirty = true;
+
+        if let Some(parent) = parent.and_then(|parent| self.slot_mut(parent)) {
+            parent.children.retain(|child| *child != id);
+        }
+
+        for child in children {
+            self.remove_object(child);
+        }
+    }
+
+    /// Parents `child` to `parent`, or detaches it with `None`. From then on
+    /// the child's current transform is treated as local to the parent, and
+    /// its own transform is overwritten with the resulting world transform
+    /// every frame. Move a parented object through `local_transform_mut`.
+    pub fn set_parent(&mut self, child: ObjectId, parent: Option<ObjectId>) -> Result<(), String> {
+        if self.slot(child).is_none() {
+            return Err(String::from("Child handle is stale"));
+        }
+
+        if let Some(parent) = parent {
+            if self.slot(parent).is_none() {
+                return Err(String::from("Parent handle is stale"));
+            }
+
+            let mut ancestor = Some(parent);
+            while let Some(id) = ancestor {
+                if id == child {
+                    return Err(String::from(
+                        "Object cannot be parented to its own descendant",
+                    ));
+                }
+                ancestor = self.slots[id.index as usize].parent;
+            }
+        }
+
+        let slot = self.slot_mut(child).expect("Checked above.");
+        let old_parent = std::mem::replace(&mut slot.parent, parent);
+        // Picked up from the object the next time the hierarchy is updated,
+        // as it may not be spawned yet.
+        slot.local_transform = None;
+
+        if let Some(old_parent) = old_parent.and_then(|old_parent| self.slot_mut(old_parent)) {
+            old_parent.children.retain(|id| *id != child);
+        }
+
+        if let Some(parent) = parent.and_then(|parent| self.slot_mut(parent)) {
+            parent.children.push(child);
+        }
+
+        Ok(())
+    }
+
+    pub fn parent(&self, id: ObjectId) -> Option<ObjectId> {
+        self.slot(id).and_then(|slot| slot.parent)
+    }
+
+    pub fn children(&self, id: ObjectId) -> &[ObjectId] {
+        match self.slot(id) {
+            Some(slot) => &slot.children,
+            None => &[],
+        }
+    }
+
+    /// The transform of a parented object relative to its parent. `None` for
+    /// objects without a parent, whose own transform is already in world space.
+    pub fn local_transform_mut(&mut self, id: ObjectId) -> Option<&mut Transform> {
+        let object = self.get(id);
+        let slot = self.slot_mut(id)?;
+        slot.parent?;
+
+        if slot.local_transform.is_none() {
+            slot.local_transform = object?.borrow().transform().cloned();
+        }
+
+        slot.local_transform.as_mut()
+    }
+
+    /// Scales the sizes and offsets of everything parented below `id`. The
+    /// object's own size is left alone.
+    pub fn set_scale(&mut self, id: ObjectId, scale: Vec2f) {
+        if let Some(slot) = self.slot_mut(id) {
+            slot.scale = scale;
+        }
+    }
+
+    /// Writes the world transform of every parented object into the object,
+    /// working down from the objects that have no parent.
+    fn update_world_transforms(&mut self) {
+        let mut stack: Vec<(ObjectId, Transform, Vec2f)> = Vec::new();
+
+        for (id, object) in self.live_objects() {
+            let slot = &self.slots[id.index as usize];
+            if slot.parent.is_some() || slot.children.is_empty() {
+                continue;
+            }
+
+            let world_transform = object
+                .borrow()
+                .transform()
+                .cloned()
+                .unwrap_or_else(|| Transform::new([0.0, 0.0].into(), [0.0, 0.0].into(), 0.0));
+
+            for child in &slot.children {
+                stack.push((*child, world_transform.clone(), slot.scale.clone()));
+            }
+        }
+
+        while let Some((id, parent_transform, scale)) = stack.pop() {
+            let object = match self.object(id) {
+                Some(object) => object,
+                None => continue,
+            };
+
+            let slot = &mut self.slots[id.index as usize];
+            if slot.local_transform.is_none() {
+                slot.local_transform = object.borrow().transform().cloned();
+            }
+
+            let world_transform = match &slot.local_transform {
+                Some(local_transform) => {
+                    let world_transform = parent_transform.compose(local_transform, &scale);
+                    object.borrow_mut().set_transform(world_transform.clone());
+                    self.index.update(id, Self::bounds(&*object.borrow()));
+                    world_transform
+                }
+                None => parent_transform,
+            };
+
+            let mut children_scale = scale;
+            children_scale *= &slot.scale;
+
+            for child in &slot.children {
+                stack.push((*child, world_transform.clone(), children_scale.clone()));
+            }
+        }
+    }
+
+    /// Static objects can opt out of ticking entirely. Works on ids that are
+    /// still waiting to be spawned.
+    pub fn set_tick_enabled(&mut self, id: ObjectId, enabled: bool) {
+        if let Some(slot) = self.slot_mut(id) {
+            slot.tick_enabled = enabled;
+            self.tick_order_dirty = true;
+        }
+    }
+
+    /// Objects with a higher priority tick earlier in the frame. Objects with
+    /// the same priority tick in the order they were spawned. Defaults to 0.
+    pub fn set_tick_priority(&mut self, id: ObjectId, priority: i32) {
+        if let Some(slot) = self.slot_mut(id) {
+            slot.tick_priority = priority;
+            self.tick_order_dirty = true;
+        }
+    }
+
+    fn slot(&self, id: ObjectId) -> Option<&Slot> {
+        self.slots
+            .get(id.index as usize)
+            .filter(|slot| slot.generation == id.generation)
+    }
+
+    fn slot_mut(&mut self, id: ObjectId) -> Option<&mut Slot> {
+        self.slots
+            .get_mut(id.index as usize)
+            .filter(|slot| slot.generation == id.generation)
+    }
+
+    fn update_tick_order(&mut self) {
+        if !self.tick_order_dirty {
+            return;
+        }
+
+        let mut tickable: Vec<(ObjectId, &Slot)> = self
+            .slots
+            .iter()
+            .enumerate()
+            .filter(|(_, slot)| slot.object.is_some() && slot.tick_enabled)
+            .map(|(index, slot)| {
+                (
+                    ObjectId {
+                        index: index as u32,
+                        generation: slot.generation,
+                    },
+                    slot,
+                )
+            })
+            .collect();
+
+        tickable.sort_by_key(|(_, slot)| (Reverse(slot.tick_priority), slot.spawn_order));
+
+        self.tick_order = tickable.into_iter().map(|(id, _)| id).collect();
+        self.tick_order_dirty = false;
+    }
+
+    /// The object behind `id`. It is assumed to move until the object that
+    /// is ticking finishes, so spatial queries see any change made through it.
+    /// Don't keep the handle past the current step: changes made through it
+    /// later go unseen by the spatial index. Call `get` again instead.
+    pub fn get(&self, id: ObjectId) -> Option<Rc<RefCell<dyn Object>>> {
+        let object = self.object(id)?;
+        self.moved.borrow_mut().insert(id);
+        Some(object)
+    }
+
+    // `get` for the manager's own reads, which move nothing.
+    fn object(&self, id: ObjectId) -> Option<Rc<RefCell<dyn Object>>> {
+        match self.slots.get(id.index as usize) {
+            Some(slot) if slot.generation == id.generation => slot.object.clone(),
+            _ => None,
+        }
+    }
+
+    /// Refiles `id` in the spatial index under its current transform. An
+    /// object that is borrowed elsewhere is refiled later.
+    fn reindex(&mut self, id: ObjectId) {
+        let object = match self.object(id) {
+            Some(object) => object,
+            None => return,
+        };
+
+        match object.try_borrow() {
+            Ok(object) => self.index.update(id, Self::bounds(&*object)),
+            Err(_) => {
+                self.moved.borrow_mut().insert(id);
+            }
+        };
+    }
+
+    // The box an object is filed under: around both the rectangle it draws
+    // in and the shape it collides as.
+    fn bounds(object: &dyn Object) -> Option<(Vec2f, Vec2f)> {
+        let transform = object.transform()?;
+        let (min, max) = transform.bounding_box();
+        if matches!(object.shape(), Shape::Box) {
+            return Some((min, max));
+        }
+
+        let (shape_min, shape_max) = object.shape().collider(transform).bounding_box();
+        Some((
+            Vec2f::new(min.x.min(shape_min.x), min.y.min(shape_min.y)),
+            Vec2f::new(max.x.max(shape_max.x), max.y.max(shape_max.y)),
+        ))
+    }
+
+    fn reindex_moved(&mut self) {
+        let moved = std::mem::take(self.moved.get_mut());
+        for id in moved {
+            self.reindex(id);
         }
     }
 
-    pub fn remove_object_tag(&mut self, tag: String) {
-        self.objects.retain(|object| {
-            if let Ok(object) = object.try_borrow() {
-                !object.tags().contains(&tag)
+    pub fn contains(&self, id: ObjectId) -> bool {
+        self.object(id).is_some()
+    }
+
+    /// Every live object, in slot order. As with `get`, each one handed out
+    /// is assumed to move, so spatial queries see any change made through it,
+    /// and none should be kept past the current step.
+    pub fn objects(&self) -> impl Iterator<Item = (ObjectId, &Rc<RefCell<dyn Object>>)> {
+        self.live_objects().inspect(|(id, _)| {
+            self.moved.borrow_mut().insert(*id);
+        })
+    }
+
+    // `objects` for the manager's own reads, which move nothing.
+    fn live_objects(&self) -> impl Iterator<Item = (ObjectId, &Rc<RefCell<dyn Object>>)> {
+        self.slots.iter().enumerate().filter_map(|(index, slot)| {
+            slot.object.as_ref().map(|object| {
+                (
+                    ObjectId {
+                        index: index as u32,
+                        generation: slot.generation,
+                    },
+                    object,
+                )
+            })
+        })
+    }
+
+    pub fn tick(ctx: &mut EngineContext, delta_time: f64) {
+        for (_, object) in ctx.om.live_objects() {
+            object.borrow_mut().begin_step();
+        }
+
+        ctx.om.update_tick_order();
+
+        for id in ctx.om.tick_order.clone() {
+            if let Some(object) = ctx.om.object(id) {
+                object.borrow_mut().tick(ctx, delta_time);
+                ctx.om.reindex(id);
+                ctx.om.reindex_moved();
+            }
+        }
+
+        ctx.om.step_physics(delta_time);
+        ctx.om.update_world_transforms();
+        Self::dispatch_collisions(ctx);
+
+        let om = &mut ctx.om;
+        om.screen_transform.position = om.camera.position.clone();
+        om.objects_on_screen = om.objects_in_bounds(&om.screen_transform);
+
+        om.camera.tick();
+        ctx.sm.update_camera(ctx.renderer.as_mut(), &ctx.om.camera);
+        ctx.batch
+            .update_camera(ctx.renderer.as_mut(), &ctx.om.camera);
+    }
+
+    /// Every overlapping pair of objects whose `collides()` is true, lower
+    /// id first, in id order.
+    pub fn collisions(&self) -> Vec<(ObjectId, ObjectId, Manifold)> {
+        let mut collisions = Vec::new();
+
+        for (id, object) in self.live_objects() {
+            let object = match object.try_borrow() {
+                Ok(object) => object,
+                Err(_) => continue,
+            };
+            let collider = match object.collider() {
+                Some(collider) if object.collides() => collider,
+                _ => continue,
+            };
+            let layer = object.layer();
+
+            let (min, max) = collider.bounding_box();
+            let bounds = Transform::new(&(&min + &max) / 2.0, &max - &min, 0.0);
+
+            for other_id in
+                self.objects_in_bounds_masked(&bounds, self.layers.collision_mask(layer))
+            {
+                if other_id <= id {
+                    continue;
+                }
+
+                let other = match self.object(other_id) {
+                    Some(other) => other,
+                    None => continue,
+                };
+                let other = match other.try_borrow() {
+                    Ok(other) => other,
+                    Err(_) => continue,
+                };
+                let other_collider = match other.collider() {
+                    Some(other_collider)
+                        if other.collides() && self.layers.collides(layer, other.layer()) =>
+                    {
+                        other_collider
+                    }
+                    _ => continue,
+                };
+
+                if let Some(manifold) = collider.collide(&other_collider) {
+                    collisions.push((id, other_id, manifold));
+                }
+            }
+        }
+
+        // Candidates come in spawn order, not id order.
+        collisions.sort_by_key(|(a, b, _)| (*a, *b));
+        collisions
+    }
+
+    /// Moves every body, then pushes overlapping pairs apart. Bodies on
+    /// parented objects are ignored, as the parent places them.
+    fn step_physics(&mut self, delta_time: f64) {
+        let mut moved: Vec<ObjectId> = Vec::new();
+        let mut swept_contacts = Vec::new();
+
+        for (id, object) in self.live_objects() {
+            if self.slots[id.index as usize].parent.is_some() {
+                continue;
+            }
+
+            let mut object = object.borrow_mut();
+            let body = match object.body_mut() {
+                Some(body) => body,
+                None => continue,
+            };
+
+            self.physics.accelerate(body, delta_time);
+            let (obstacle_ids, obstacles): (Vec<ObjectId>, Vec<Collider>) = if body.continuous {
+                self.obstacles(&*object, delta_time).into_iter().unzip()
             } else {
-                true
+                (Vec::new(), Vec::new())
+            };
+
+            let hits = Physics::advance(&mut *object, delta_time, &obstacles);
+            moved.push(id);
+
+            let center = match object.collider() {
+                Some(collider) => collider.center(),
+                None => continue,
+            };
+            for hit in hits {
+                let other = obstacle_ids[hit.index];
+                let manifold = Manifold {
+                    normal: hit.normal,
+                    depth: 0.0,
+                    points: vec![&(&center + &obstacles[hit.index].center()) / 2.0],
+                };
+
+                swept_contacts.push(if id < other {
+                    (id, other, manifold)
+                } else {
+                    (other, id, manifold.flipped())
+                });
             }
-        });
+        }
+
+        self.swept_contacts = swept_contacts;
+        for id in moved {
+            self.reindex(id);
+        }
+
+        // Only pairs with something that can be pushed. Static and kinematic
+        // bodies go wherever they are put.
+        let pairs: Vec<(ObjectId, ObjectId)> = self
+            .collisions()
+            .into_iter()
+            .filter(|(a, b, _)| self.is_dynamic(*a) || self.is_dynamic(*b))
+            .map(|(a, b, _)| (a, b))
+            .collect();
+
+        for _ in 0..self.physics.iterations {
+            for (a, b) in &pairs {
+                let (object_a, object_b) = match (self.object(*a), self.object(*b)) {
+                    (Some(object_a), Some(object_b)) => (object_a, object_b),
+                    _ => continue,
+                };
+                let mut object_a = object_a.borrow_mut();
+                let mut object_b = object_b.borrow_mut();
+
+                let manifold = match (object_a.collider(), object_b.collider()) {
+                    (Some(collider_a), Some(collider_b)) => collider_a.collide(&collider_b),
+                    _ => None,
+                };
+
+                if let Some(manifold) = manifold {
+                    self.physics
+                        .resolve(&mut *object_a, &mut *object_b, &manifold);
+                }
+            }
+        }
+
+        for (a, b) in pairs {
+            self.reindex(a);
+            self.reindex(b);
+        }
     }
 
-    pub fn tick(&mut self, delta_time: f64) {
-        self.screen_transform.position = self.camera.position.clone();
+    // The colliders a continuous body could reach this step, if it collides.
+    // The body itself is borrowed, so it isn't among them.
+    fn obstacles(&self, object: &dyn Object, delta_time: f64) -> Vec<(ObjectId, Collider)> {
+        let (collider, body) = match (object.collider(), object.body()) {
+            (Some(collider), Some(body)) if object.collides() => (collider, body),
+            _ => return Vec::new(),
+        };
 
-        self.objects_on_screen = self.objects_in_bounds(&self.screen_transform);
+        let (min, max) = collider.bounding_box();
+        let displacement = &body.velocity * delta_time;
+        let swept_min = Vec2f::new(
+            min.x + displacement.x.min(0.0),
+            min.y + displacement.y.min(0.0),
+        );
+        let swept_max = Vec2f::new(
+            max.x + displacement.x.max(0.0),
+            max.y + displacement.y.max(0.0),
+        );
+        let bounds = Transform::new(
+            &(&swept_min + &swept_max) / 2.0,
+            &swept_max - &swept_min,
+            0.0,
+        );
 
-        self.objects[0].borrow_mut().tick(delta_time);
+        self.objects_in_bounds_masked(&bounds, self.layers.collision_mask(object.layer()))
+            .into_iter()
+            .filter_map(|id| {
+                let other = self.object(id)?;
+                let other = other.try_borrow().ok()?;
+                if !other.collides() {
+                    return None;
+                }
+                other.collider().map(|collider| (id, collider))
+            })
+            .collect()
+    }
 
-        self.camera.tick();
-        sm().update_camera(&self.camera);
+    fn is_dynamic(&self, id: ObjectId) -> bool {
+        self.object(id).is_some_and(|object| {
+            object
+                .borrow()
+                .body()
+                .is_some_and(|body| body.kind == BodyKind::Dynamic)
+        })
     }
 
-    pub fn draw(&self) {
+    // Tells both objects of every pair about their contact: ended contacts
+    // first, then current ones. Pairs a continuous body swept into count as
+    // touching.
+    fn dispatch_collisions(ctx: &mut EngineContext) {
+        let mut collisions = ctx.om.collisions();
+        for (a, b, manifold) in std::mem::take(&mut ctx.om.swept_contacts) {
+            if !collisions.iter().any(|(x, y, _)| (*x, *y) == (a, b)) {
+                collisions.push((a, b, manifold));
+            }
+        }
+        collisions.sort_by_key(|(a, b, _)| (*a, *b));
+        let (began, ended) = ctx.om.contacts.update(&collisions);
+
+        for (a, b) in ended {
+            Self::notify(ctx, a, |object, ctx| object.on_collision_exit(ctx, b));
+            Self::notify(ctx, b, |object, ctx| object.on_collision_exit(ctx, a));
+        }
+
+        for (a, b, manifold) in collisions {
+            let collision_a = Collision {
+                other: b,
+                manifold: manifold.clone(),
+            };
+            let collision_b = Collision {
+                other: a,
+                manifold: manifold.flipped(),
+            };
+
+            if began.contains(&(a, b)) {
+                Self::notify(ctx, a, |object, ctx| {
+                    object.on_collision_enter(ctx, &collision_a)
+                });
+                Self::notify(ctx, b, |object, ctx| {
+                    object.on_collision_enter(ctx, &collision_b)
+                });
+            } else {
+                Self::notify(ctx, a, |object, ctx| {
+                    object.on_collision_stay(ctx, &collision_a)
+                });
+                Self::notify(ctx, b, |object, ctx| {
+                    object.on_collision_stay(ctx, &collision_b)
+                });
+            }
+        }
+    }
+
+    fn notify(
+        ctx: &mut EngineContext,
+        id: ObjectId,
+        callback: impl FnOnce(&mut dyn Object, &mut EngineContext),
+    ) {
+        if let Some(object) = ctx.om.object(id) {
+            callback(&mut *object.borrow_mut(), ctx);
+            ctx.om.reindex(id);
+            ctx.om.reindex_moved();
+        }
+    }
+
+    pub fn draw(ctx: &mut EngineContext) {
         let mut curr_shader_id = -1;
-        for object in &self.objects_on_screen {
+        for id in ctx.om.objects_on_screen.clone() {
+            let object = match ctx.om.object(id) {
+                Some(object) => object,
+                None => continue,
+            };
             let object = object.borrow();
-            match object.shader() {
-                None => {}
-                Some(shader) => {
-                    if shader.id() != curr_shader_id {
-                        curr_shader_id = shader.id();
-                        shader.bind();
-                    }
+            if let Some(shader) = object.shader() {
+                // Queued sprites go first, or they'd end up on top.
+                if ctx.batch.flush(ctx.renderer.as_mut()) {
+                    curr_shader_id = ctx.batch.shader().id();
                 }
-            };
+                if shader.id() != curr_shader_id {
+                    curr_shader_id = shader.id();
+                    shader.bind(ctx.renderer.as_mut());
+                }
+            }
 
-            object.draw();
+            object.draw(ctx);
         }
+
+        ctx.batch.flush(ctx.renderer.as_mut());
     }
 
-    pub fn objects_in_bounds(&self, transform: &Transform) -> Vec<Rc<RefCell<dyn Object>>> {
-        let mut objects_in_bounds: Vec<Rc<RefCell<dyn Object>>> = Vec::new();
+    /// Where something that was at `previous` when the step began and is at
+    /// `current` now should be drawn this frame. Without a previous transform
+    /// there is nothing to blend from.
+    pub fn interpolate(&self, previous: Option<&Transform>, current: &Transform) -> Transform {
+        match previous {
+            Some(previous) => previous.lerp(current, self.interpolation_alpha),
+            None => current.clone(),
+        }
+    }
+
+    /// Encodes whatever the renderer has drawn this frame as a PNG.
+    pub fn capture_png(ctx: &EngineContext) -> Result<Vec<u8>, String> {
+        let frame = ctx
+            .renderer
+            .frame()
+            .ok_or_else(|| String::from("Renderer cannot read back frames"))?;
+
+        let mut png = Vec::new();
+        frame
+            .write_to(&mut Cursor::new(&mut png), ImageFormat::Png)
+            .map_err(|e| e.to_string())?;
+
+        Ok(png)
+    }
+
+    /// The topmost object, in draw order, whose shape contains `point`.
+    pub fn pick(&self, point: &Vec2f) -> Option<ObjectId> {
+        self.objects_at_point(point, &QueryFilter::default()).pop()
+    }
+
+    /// The topmost object under the pointer.
+    pub fn object_under_pointer(&self, input: &Input) -> Option<ObjectId> {
+        self.pick(&input.pointer.world_position(&self.camera))
+    }
+
+    /// The objects whose bounding boxes overlap `transform`, taken as
+    /// unrotated, in spawn order. Candidates come from the spatial index, plus
+    /// anything that may have moved since it was last updated.
+    pub fn objects_in_bounds(&self, transform: &Transform) -> Vec<ObjectId> {
+        self.objects_in_bounds_masked(transform, LayerMask::ALL)
+    }
+
+    /// `objects_in_bounds` for objects on one of the layers in `mask`.
+    pub fn objects_in_bounds_masked(
+        &self,
+        transform: &Transform,
+        mask: LayerMask,
+    ) -> Vec<ObjectId> {
+        let mut objects_in_bounds: Vec<ObjectId> = Vec::new();
+
+        let half_size = &transform.size.abs() / 2.0;
+        let mut candidates = self.index.query(
+            &(&transform.position - &half_size),
+            &(&transform.position + &half_size),
+        );
+        candidates.extend(self.moved.borrow().iter());
+        // Spawn order is draw order, whichever slots the objects reuse.
+        candidates.sort_unstable_by_key(|id| self.slots[id.index as usize].spawn_order);
+        candidates.dedup();
 
-        for object in &self.objects {
+        for id in candidates {
+            let object = match self.object(id) {
+                Some(object) => object,
+                None => continue,
+            };
             let object_ref = match object.try_borrow() {
                 Ok(r) => r,
                 Err(_) => {
@@ -112,16 +871,151 @@ impl ObjectManager {
                 }
             };
 
-            match object_ref.transform() {
-                None => {}
-                Some(transform2) => {
-                    if transform2.overlaps_lazy(transform) {
-                        objects_in_bounds.push(object.clone());
-                    }
+            match Self::bounds(&*object_ref) {
+                Some((min, max))
+                    if object_ref.layer().intersects(mask)
+                        && min.x < transform.position.x + half_size.x
+                        && max.x > transform.position.x - half_size.x
+                        && min.y < transform.position.y + half_size.y
+                        && max.y > transform.position.y - half_size.y =>
+                {
+                    objects_in_bounds.push(id);
                 }
+                _ => {}
             };
         }
 
         objects_in_bounds
     }
+
+    /// The objects whose shapes contain `point`, in spawn order.
+    pub fn objects_at_point(&self, point: &Vec2f, filter: &QueryFilter) -> Vec<ObjectId> {
+        self.query_candidates(point, point, filter)
+            .into_iter()
+            .filter(|(_, collider)| collider.contains_point(point))
+            .map(|(id, _)| id)
+            .collect()
+    }
+
+    /// The objects `shape` overlaps, in spawn order.
+    pub fn overlap(&self, shape: &Collider, filter: &QueryFilter) -> Vec<ObjectId> {
+        let (min, max) = shape.bounding_box();
+
+        self.query_candidates(&min, &max, filter)
+            .into_iter()
+            .filter(|(_, collider)| shape.overlaps(collider))
+            .map(|(id, _)| id)
+            .collect()
+    }
+
+    /// Every object a ray from `origin` along `direction` meets within
+    /// `max_distance`, nearest first. A ray starting inside an object hits it
+    /// at distance 0.
+    pub fn ray_cast_all(
+        &self,
+        origin: &Vec2f,
+        direction: &Vec2f,
+        max_distance: f64,
+        filter: &QueryFilter,
+    ) -> Vec<QueryHit> {
+        let point = Collider::circle(origin.clone(), 0.0);
+
+        self.shape_cast_all(&point, direction, max_distance, filter)
+    }
+
+    /// The nearest object a ray meets, as in `ray_cast_all`.
+    pub fn ray_cast(
+        &self,
+        origin: &Vec2f,
+        direction: &Vec2f,
+        max_distance: f64,
+        filter: &QueryFilter,
+    ) -> Option<QueryHit> {
+        self.ray_cast_all(origin, direction, max_distance, filter)
+            .into_iter()
+            .next()
+    }
+
+    /// Every object `shape` touches when moved up to `max_distance` along
+    /// `direction`, nearest first. Objects it starts out overlapping are hit
+    /// at distance 0.
+    pub fn shape_cast_all(
+        &self,
+        shape: &Collider,
+        direction: &Vec2f,
+        max_distance: f64,
+        filter: &QueryFilter,
+    ) -> Vec<QueryHit> {
+        let direction = direction.normalize();
+        if direction.len() == 0.0 {
+            return Vec::new();
+        }
+
+        let max_distance = max_distance.min(MAX_QUERY_DISTANCE);
+        let reach = &direction * max_distance;
+        let (min, max) = shape.bounding_box();
+        let center = shape.center();
+        let swept_min = Vec2f::new(min.x + reach.x.min(0.0), min.y + reach.y.min(0.0));
+        let swept_max = Vec2f::new(max.x + reach.x.max(0.0), max.y + reach.y.max(0.0));
+
+        let mut hits: Vec<QueryHit> = self
+            .query_candidates(&swept_min, &swept_max, filter)
+            .into_iter()
+            .filter_map(|(id, collider)| {
+                let (distance, normal) = shape.cast(&direction, max_distance, &collider)?;
+
+                Some(QueryHit {
+                    object: id,
+                    pos: &center + &(&direction * distance),
+                    distance,
+                    normal,
+                })
+            })
+            .collect();
+
+        hits.sort_by(|a, b| a.distance.total_cmp(&b.distance));
+        hits
+    }
+
+    /// The nearest object `shape` touches, as in `shape_cast_all`.
+    pub fn shape_cast(
+        &self,
+        shape: &Collider,
+        direction: &Vec2f,
+        max_distance: f64,
+        filter: &QueryFilter,
+    ) -> Option<QueryHit> {
+        self.shape_cast_all(shape, direction, max_distance, filter)
+            .into_iter()
+            .next()
+    }
+
+    // The objects `filter` accepts whose bounding boxes overlap the box from
+    // `min` to `max`, with their colliders.
+    fn query_candidates(
+        &self,
+        min: &Vec2f,
+        max: &Vec2f,
+        filter: &QueryFilter,
+    ) -> Vec<(ObjectId, Collider)> {
+        let bounds = Transform::new(&(min + max) / 2.0, max - min, 0.0);
+
+        self.objects_in_bounds_masked(&bounds, filter.mask)
+            .into_iter()
+            .filter_map(|id| {
+                let object = self.object(id)?;
+                let object = object.try_borrow().ok()?;
+                if !filter.accepts(id, &*object) {
+                    return None;
+                }
+                object.collider().map(|collider| (id, collider))
+            })
+            .collect()
+    }
+}
+
+impl Default for ObjectManager {
+    fn default() -> Self {
+        Self::new()
+    }
 }
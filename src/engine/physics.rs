@@ -0,0 +1,266 @@
+use crate::engine::collision::{Manifold, SweepHit};
+use crate::engine::object::Object;
+use crate::engine::shape::{Collider, Shape};
+use crate::engine::transform::Transform;
+use crate::engine::vec2f::Vec2f;
+
+/// How much of the overlap is left unresolved, so bodies at rest keep
+/// touching rather than flickering in and out of contact.
+const SLOP: f64 = 0.01;
+
+/// How far short of a surface `move_and_slide` stops.
+const SKIN: f64 = 0.001;
+
+/// How many surfaces `move_and_slide` slides along in one move.
+const MAX_SLIDES: usize = 4;
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum BodyKind {
+    /// Never moves.
+    Static,
+    /// Moves at its velocity, pushing dynamic bodies without being pushed.
+    Kinematic,
+    /// Moves under gravity, acceleration and contacts.
+    Dynamic,
+}
+
+/// Motion for an object that exposes one through `Object::body`. Only
+/// objects whose `collides()` is true take part in contacts. Objects that
+/// collide without a body are treated as static.
+#[derive(Clone, PartialEq, Debug)]
+pub struct RigidBody {
+    pub kind: BodyKind,
+    /// World units per second.
+    pub velocity: Vec2f,
+    /// World units per second squared, on top of gravity.
+    pub acceleration: Vec2f,
+    /// Radians per second.
+    pub angular_velocity: f64,
+    pub mass: f64,
+    /// 0 for no bounce, 1 for a perfectly elastic one.
+    pub restitution: f64,
+    pub friction: f64,
+    /// Multiplies `Physics::gravity` for this body.
+    pub gravity_scale: f64,
+    /// Sweeps the body along its movement each step so it can't tunnel
+    /// through thin colliders, sliding along whatever it meets. Costs a
+    /// query per step, so keep it for fast movers.
+    pub continuous: bool,
+}
+
+impl RigidBody {
+    pub fn new(kind: BodyKind) -> Self {
+        Self {
+            kind,
+            velocity: [0.0, 0.0].into(),
+            acceleration: [0.0, 0.0].into(),
+            angular_velocity: 0.0,
+            mass: 1.0,
+            restitution: 0.0,
+            friction: 0.2,
+            gravity_scale: 1.0,
+            continuous: false,
+        }
+    }
+
+    pub fn dynamic() -> Self {
+        Self::new(BodyKind::Dynamic)
+    }
+
+    pub fn kinematic() -> Self {
+        Self::new(BodyKind::Kinematic)
+    }
+
+    pub fn fixed() -> Self {
+        Self::new(BodyKind::Static)
+    }
+
+    fn inverse_mass(&self) -> f64 {
+        match self.kind {
+            BodyKind::Dynamic if self.mass > 0.0 => 1.0 / self.mass,
+            _ => 0.0,
+        }
+    }
+}
+
+/// World settings for the physics step run by `ObjectManager::tick`.
+pub struct Physics {
+    /// World units per second squared. y points up.
+    pub gravity: Vec2f,
+    /// How many times overlapping pairs are pushed apart each step. More
+    /// settles stacks faster.
+    pub iterations: u32,
+    /// Closing speed below which contacts don't bounce, so bodies resting
+    /// under gravity settle instead of hopping in place.
+    pub restitution_threshold: f64,
+}
+
+impl Physics {
+    pub fn new() -> Self {
+        Self {
+            gravity: [0.0, 0.0].into(),
+            iterations: 4,
+            restitution_threshold: 50.0,
+        }
+    }
+
+    /// Applies gravity and acceleration to the velocity of a dynamic body.
+    pub fn accelerate(&self, body: &mut RigidBody, delta_time: f64) {
+        if body.kind == BodyKind::Dynamic {
+            let acceleration = &body.acceleration + &(&self.gravity * body.gravity_scale);
+            body.velocity += &(&acceleration * delta_time);
+        }
+    }
+
+    /// Moves a dynamic or kinematic body by its velocity. Continuous bodies
+    /// move and slide against `obstacles`, losing the velocity they had into
+    /// whatever they hit. Returns what they hit, as `move_and_slide` does.
+    pub fn advance(
+        object: &mut dyn Object,
+        delta_time: f64,
+        obstacles: &[Collider],
+    ) -> Vec<SweepHit> {
+        let body = match object.body() {
+            Some(body) if body.kind != BodyKind::Static => body.clone(),
+            _ => return Vec::new(),
+        };
+        let shape = object.shape().clone();
+        let transform = match object.transform_mut() {
+            Some(transform) => transform,
+            None => return Vec::new(),
+        };
+
+        let mut velocity = body.velocity.clone();
+        let hits = if body.continuous {
+            Self::move_and_slide(transform, &shape, &mut velocity, delta_time, obstacles)
+        } else {
+            transform.position += &(&velocity * delta_time);
+            Vec::new()
+        };
+        transform.rotation += body.angular_velocity * delta_time;
+
+        if let Some(body) = object.body_mut() {
+            body.velocity = velocity;
+        }
+
+        hits
+    }
+
+    /// Moves `transform`, colliding as `shape`, by `velocity` for
+    /// `delta_time` seconds, stopping at the first of `others` in the way
+    /// and spending the rest of the movement sliding along it. The part of
+    /// `velocity` going into each surface hit is removed. Returns the hits
+    /// in order.
+    pub fn move_and_slide(
+        transform: &mut Transform,
+        shape: &Shape,
+        velocity: &mut Vec2f,
+        delta_time: f64,
+        others: &[Collider],
+    ) -> Vec<SweepHit> {
+        let mut hits = Vec::new();
+        let mut remaining = &*velocity * delta_time;
+
+        for _ in 0..MAX_SLIDES {
+            let distance = remaining.len();
+            if distance == 0.0 {
+                break;
+            }
+
+            let hit = match shape.collider(transform).sweep(&remaining, others) {
+                None => {
+                    transform.position += &remaining;
+                    break;
+                }
+                Some(hit) => hit,
+            };
+
+            let time = (hit.time - SKIN / distance).max(0.0);
+            transform.position += &(&remaining * time);
+            remaining = &remaining * (1.0 - time);
+
+            let into = remaining.dot(&hit.normal);
+            if into > 0.0 {
+                remaining -= &(&hit.normal * into);
+            }
+            let into = velocity.dot(&hit.normal);
+            if into > 0.0 {
+                *velocity -= &(&hit.normal * into);
+            }
+
+            hits.push(hit);
+        }
+
+        hits
+    }
+
+    /// Pushes `a` and `b` apart along the manifold's normal, split by inverse
+    /// mass, and if they are approaching, exchanges the impulse that makes
+    /// them bounce and the friction that slows their sliding.
+    pub fn resolve(&self, a: &mut dyn Object, b: &mut dyn Object, manifold: &Manifold) {
+        let (inverse_mass_a, velocity_a, restitution_a, friction_a) = Self::response(a);
+        let (inverse_mass_b, velocity_b, restitution_b, friction_b) = Self::response(b);
+
+        let inverse_mass_sum = inverse_mass_a + inverse_mass_b;
+        if inverse_mass_sum == 0.0 {
+            return;
+        }
+
+        let normal = &manifold.normal;
+
+        let correction = &(normal * ((manifold.depth - SLOP).max(0.0) / inverse_mass_sum));
+        if let Some(transform) = a.transform_mut() {
+            transform.position -= &(correction * inverse_mass_a);
+        }
+        if let Some(transform) = b.transform_mut() {
+            transform.position += &(correction * inverse_mass_b);
+        }
+
+        let relative_velocity = &velocity_b - &velocity_a;
+        let normal_speed = relative_velocity.dot(normal);
+        if normal_speed >= 0.0 {
+            return;
+        }
+
+        let restitution = if -normal_speed < self.restitution_threshold {
+            0.0
+        } else {
+            restitution_a.max(restitution_b)
+        };
+        let impulse = -(1.0 + restitution) * normal_speed / inverse_mass_sum;
+        let mut impulse_vector = normal * impulse;
+
+        let tangent = (&relative_velocity - &(normal * normal_speed)).normalize();
+        let friction = (friction_a * friction_b).sqrt();
+        let friction_impulse = (-relative_velocity.dot(&tangent) / inverse_mass_sum)
+            .clamp(-impulse * friction, impulse * friction);
+        impulse_vector += &(&tangent * friction_impulse);
+
+        if let Some(body) = a.body_mut() {
+            body.velocity -= &(&impulse_vector * inverse_mass_a);
+        }
+        if let Some(body) = b.body_mut() {
+            body.velocity += &(&impulse_vector * inverse_mass_b);
+        }
+    }
+
+    // Inverse mass, velocity, restitution and friction, with objects that
+    // have no body acting as static ones.
+    fn response(object: &dyn Object) -> (f64, Vec2f, f64, f64) {
+        let fixed = RigidBody::fixed();
+        let body = object.body().unwrap_or(&fixed);
+
+        (
+            body.inverse_mass(),
+            body.velocity.clone(),
+            body.restitution,
+            body.friction,
+        )
+    }
+}
+
+impl Default for Physics {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,60 @@
+use crate::engine::layer::LayerMask;
+use crate::engine::object::Object;
+use crate::engine::object_manager::ObjectId;
+use crate::engine::vec2f::Vec2f;
+
+/// Which objects an `ObjectManager` query considers.
+#[derive(Clone, PartialEq, Debug)]
+pub struct QueryFilter {
+    /// Only objects on one of these layers are found.
+    pub mask: LayerMask,
+    /// Objects never found, such as whoever is asking.
+    pub exclude: Vec<ObjectId>,
+    /// Whether objects whose `collides()` is false are skipped.
+    pub colliders_only: bool,
+}
+
+impl QueryFilter {
+    pub fn new(mask: LayerMask) -> Self {
+        Self {
+            mask,
+            exclude: Vec::new(),
+            colliders_only: false,
+        }
+    }
+
+    pub fn excluding(mut self, id: ObjectId) -> Self {
+        self.exclude.push(id);
+        self
+    }
+
+    pub fn colliders_only(mut self) -> Self {
+        self.colliders_only = true;
+        self
+    }
+
+    pub fn accepts(&self, id: ObjectId, object: &dyn Object) -> bool {
+        object.layer().intersects(self.mask)
+            && !self.exclude.contains(&id)
+            && (!self.colliders_only || object.collides())
+    }
+}
+
+impl Default for QueryFilter {
+    fn default() -> Self {
+        Self::new(LayerMask::ALL)
+    }
+}
+
+/// An object hit by a ray or shape cast.
+#[derive(Clone, PartialEq, Debug)]
+pub struct QueryHit {
+    pub object: ObjectId,
+    /// Where a ray meets the object's surface, or where a cast shape's
+    /// center is when it first touches the object.
+    pub pos: Vec2f,
+    /// How far along the cast the hit happens.
+    pub distance: f64,
+    /// Unit vector pointing out of the surface that was hit.
+    pub normal: Vec2f,
+}
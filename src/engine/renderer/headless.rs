@@ -0,0 +1,160 @@
+use crate::engine::camera::Camera;
+use crate::engine::renderer::{Renderer, Uniform, VertexLayout};
+use crate::engine::sprite_batch::Sprite;
+use crate::engine::transform::Transform;
+use image::RgbaImage;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct DrawCommand {
+    pub shader: i32,
+    pub transform: Option<Transform>,
+    pub color: Option<[f32; 4]>,
+    pub texture: Option<i32>,
+    pub vertex_count: i32,
+}
+
+/// A quad drawn through a `SpriteBatch`. Batched quads are recorded one by
+/// one rather than as the single draw they make up.
+#[derive(Clone, PartialEq, Debug)]
+pub struct SpriteCommand {
+    pub shader: i32,
+    pub transform: Transform,
+    pub color: [f32; 4],
+    pub texture: Option<i32>,
+    pub uv: [f32; 4],
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub enum RenderCommand {
+    Clear([f32; 4]),
+    Draw(DrawCommand),
+    Sprite(SpriteCommand),
+}
+
+pub type CommandList = Rc<RefCell<Vec<RenderCommand>>>;
+
+#[derive(Default)]
+struct ShaderState {
+    transform: Option<Transform>,
+    color: Option<[f32; 4]>,
+}
+
+/// Renderer that draws nothing and instead records every clear and draw call
+/// into a `CommandList`, so scenes can be inspected without a browser or GPU.
+pub struct HeadlessRenderer {
+    commands: CommandList,
+    shaders: Vec<ShaderState>,
+    texture_count: i32,
+    mesh_vert_counts: Vec<i32>,
+    mesh_layouts: Vec<VertexLayout>,
+    curr_shader: i32,
+    curr_texture: Option<i32>,
+}
+
+impl HeadlessRenderer {
+    pub fn new() -> Self {
+        Self {
+            commands: Rc::new(RefCell::new(Vec::new())),
+            shaders: Vec::new(),
+            texture_count: 0,
+            mesh_vert_counts: Vec::new(),
+            mesh_layouts: Vec::new(),
+            curr_shader: -1,
+            curr_texture: None,
+        }
+    }
+
+    /// Shared handle to the recorded commands. Stays valid after the renderer
+    /// has been handed to the engine.
+    pub fn commands(&self) -> CommandList {
+        self.commands.clone()
+    }
+}
+
+impl Default for HeadlessRenderer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Renderer for HeadlessRenderer {
+    fn create_shader(&mut self, _vert_src: &str, _frag_src: &str) -> Result<i32, String> {
+        self.shaders.push(ShaderState::default());
+        Ok(self.shaders.len() as i32 - 1)
+    }
+
+    fn create_texture(&mut self, _image: &RgbaImage) -> i32 {
+        self.texture_count += 1;
+        self.texture_count - 1
+    }
+
+    fn create_mesh(&mut self, vertices: &[f32], layout: VertexLayout) -> i32 {
+        self.mesh_vert_counts
+            .push((vertices.len() / layout.floats_per_vertex()) as i32);
+        self.mesh_layouts.push(layout);
+        self.mesh_vert_counts.len() as i32 - 1
+    }
+
+    fn update_mesh(&mut self, mesh: i32, vertices: &[f32]) {
+        let layout = self.mesh_layouts[mesh as usize];
+        self.mesh_vert_counts[mesh as usize] = (vertices.len() / layout.floats_per_vertex()) as i32;
+    }
+
+    fn bind_shader(&mut self, shader: i32) {
+        self.curr_shader = shader;
+    }
+
+    fn bind_texture(&mut self, texture: i32) {
+        self.curr_texture = Some(texture);
+    }
+
+    fn uniform(&mut self, shader: i32, name: &str, value: Uniform) {
+        if let ("fragColor", Uniform::Vec4(color)) = (name, value) {
+            self.shaders[shader as usize].color = Some(color);
+        }
+    }
+
+    fn uniform_transform(&mut self, shader: i32, transform: &Transform) {
+        self.shaders[shader as usize].transform = Some(transform.clone());
+    }
+
+    fn uniform_camera(&mut self, _shader: i32, _camera: &Camera) {}
+
+    fn draw_mesh(&mut self, mesh: i32) {
+        let shader = &self.shaders[self.curr_shader as usize];
+
+        self.commands
+            .borrow_mut()
+            .push(RenderCommand::Draw(DrawCommand {
+                shader: self.curr_shader,
+                transform: shader.transform.clone(),
+                color: shader.color,
+                texture: self.curr_texture,
+                vertex_count: self.mesh_vert_counts[mesh as usize],
+            }));
+    }
+
+    fn draw_sprites(&mut self, _mesh: i32, sprites: &[Sprite]) {
+        let mut commands = self.commands.borrow_mut();
+
+        for sprite in sprites {
+            commands.push(RenderCommand::Sprite(SpriteCommand {
+                shader: self.curr_shader,
+                transform: sprite.transform.clone(),
+                color: sprite.color,
+                texture: self.curr_texture,
+                uv: sprite.uv,
+            }));
+        }
+    }
+
+    fn clear(&mut self, color: [f32; 4]) {
+        self.commands.borrow_mut().push(RenderCommand::Clear(color));
+    }
+
+    fn frame(&self) -> Option<RgbaImage> {
+        None
+    }
+}
@@ -0,0 +1,68 @@
+use crate::engine::camera::Camera;
+use crate::engine::sprite_batch::Sprite;
+use crate::engine::transform::Transform;
+use image::RgbaImage;
+
+pub mod headless;
+pub mod software;
+
+/// How the floats handed to `Renderer::create_mesh` are laid out per vertex.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum VertexLayout {
+    /// `x, y`
+    Position,
+    /// `x, y, u, v`
+    PositionTexCoord,
+    /// `x, y, u, v, r, g, b, a`, already placed on screen, so only the
+    /// camera applies.
+    Sprite,
+}
+
+impl VertexLayout {
+    pub fn floats_per_vertex(&self) -> usize {
+        match self {
+            VertexLayout::Position => 2,
+            VertexLayout::PositionTexCoord => 4,
+            VertexLayout::Sprite => 8,
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Uniform {
+    Float(f32),
+    Int(i32),
+    Vec2([f32; 2]),
+    Vec4([f32; 4]),
+}
+
+/// Everything the engine needs from a graphics backend. Shaders, textures and
+/// meshes are referred to by the ids the renderer hands out when creating them.
+pub trait Renderer {
+    fn create_shader(&mut self, vert_src: &str, frag_src: &str) -> Result<i32, String>;
+    fn create_texture(&mut self, image: &RgbaImage) -> i32;
+    fn create_mesh(&mut self, vertices: &[f32], layout: VertexLayout) -> i32;
+    /// Replaces a mesh's vertices. The mesh then draws however many there
+    /// are now.
+    fn update_mesh(&mut self, mesh: i32, vertices: &[f32]);
+
+    fn bind_shader(&mut self, shader: i32);
+    fn bind_texture(&mut self, texture: i32);
+
+    fn uniform(&mut self, shader: i32, name: &str, value: Uniform);
+    fn uniform_transform(&mut self, shader: i32, transform: &Transform);
+    fn uniform_camera(&mut self, shader: i32, camera: &Camera);
+
+    fn draw_mesh(&mut self, mesh: i32);
+    /// Draws the quads a `SpriteBatch` queued, with the bound shader and
+    /// texture, by filling `mesh` with their vertices.
+    fn draw_sprites(&mut self, mesh: i32, sprites: &[Sprite]) {
+        let vertices: Vec<f32> = sprites.iter().flat_map(Sprite::vertices).collect();
+        self.update_mesh(mesh, &vertices);
+        self.draw_mesh(mesh);
+    }
+    fn clear(&mut self, color: [f32; 4]);
+
+    /// Reads back what has been drawn so far, if the backend has pixels.
+    fn frame(&self) -> Option<RgbaImage>;
+}
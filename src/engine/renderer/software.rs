@@ -0,0 +1,296 @@
+use crate::engine::camera::Camera;
+use crate::engine::renderer::{Renderer, Uniform, VertexLayout};
+use crate::engine::transform::Transform;
+use image::{Rgba, RgbaImage};
+
+struct ShaderState {
+    transform: Transform,
+    camera_position: [f64; 2],
+    camera_zoom: f64,
+    color: [f32; 4],
+}
+
+struct SoftwareMesh {
+    vertices: Vec<f32>,
+    layout: VertexLayout,
+}
+
+/// Screen-space vertex: pixel coordinates plus texture coordinates.
+#[derive(Clone, Copy)]
+struct Fragment {
+    x: f64,
+    y: f64,
+    u: f64,
+    v: f64,
+}
+
+/// CPU renderer that rasterizes into an `RgbaImage`, following the same maths
+/// as `colored_vert.glsl` and `textured_vert.glsl`.
+pub struct SoftwareRenderer {
+    frame: RgbaImage,
+    shaders: Vec<ShaderState>,
+    textures: Vec<RgbaImage>,
+    meshes: Vec<SoftwareMesh>,
+    curr_shader: i32,
+    curr_texture: Option<i32>,
+}
+
+impl SoftwareRenderer {
+    pub fn new(width: u32, height: u32) -> Self {
+        Self {
+            frame: RgbaImage::new(width, height),
+            shaders: Vec::new(),
+            textures: Vec::new(),
+            meshes: Vec::new(),
+            curr_shader: -1,
+            curr_texture: None,
+        }
+    }
+
+    /// Mirrors `sprite_vert.glsl`, for vertices that are already placed.
+    fn sprite_to_ndc(shader: &ShaderState, x: f64, y: f64) -> (f64, f64) {
+        (
+            (x - shader.camera_position[0] / 960.0) * shader.camera_zoom,
+            (y - shader.camera_position[1] / 540.0) * shader.camera_zoom,
+        )
+    }
+
+    /// Mirrors the vertex shaders, returning normalized device coordinates.
+    fn vertex_to_ndc(shader: &ShaderState, x: f64, y: f64) -> (f64, f64) {
+        let transform = &shader.transform;
+
+        let x = x * transform.size.x / 960.0;
+        let y = y * transform.size.y / 960.0;
+
+        let (sin, cos) = transform.rotation.sin_cos();
+        let x_rotated = x * cos - y * sin;
+        let y_rotated = y * cos + x * sin;
+
+        let y_rotated = y_rotated * 16.0 / 9.0;
+
+        (
+            (x_rotated + transform.position.x / 960.0 - shader.camera_position[0] / 960.0)
+                * shader.camera_zoom,
+            (y_rotated + transform.position.y / 540.0 - shader.camera_position[1] / 540.0)
+                * shader.camera_zoom,
+        )
+    }
+
+    fn sample(texture: &RgbaImage, u: f64, v: f64) -> [f32; 4] {
+        let (width, height) = texture.dimensions();
+
+        let x = (u.rem_euclid(1.0) * width as f64) as u32;
+        let y = (v.rem_euclid(1.0) * height as f64) as u32;
+
+        texture
+            .get_pixel(x.min(width - 1), y.min(height - 1))
+            .0
+            .map(|c| c as f32 / 255.0)
+    }
+
+    fn blend(&mut self, x: u32, y: u32, src: [f32; 4]) {
+        let dst = self.frame.get_pixel(x, y).0.map(|c| c as f32 / 255.0);
+        let alpha = src[3];
+
+        let mut out = [0u8; 4];
+        for i in 0..4 {
+            let c = src[i] * alpha + dst[i] * (1.0 - alpha);
+            out[i] = (c.clamp(0.0, 1.0) * 255.0).round() as u8;
+        }
+
+        self.frame.put_pixel(x, y, Rgba(out));
+    }
+
+    fn rasterize_triangle(&mut self, tri: [Fragment; 3], color: [f32; 4], texture: Option<i32>) {
+        let [a, b, c] = tri;
+
+        let area = edge(&a, &b, c.x, c.y);
+        if area == 0.0 {
+            return;
+        }
+
+        // Work with a consistent winding so the top-left rule below applies
+        // the same way to both triangles of a quad.
+        let (b, c, area) = if area < 0.0 {
+            (c, b, -area)
+        } else {
+            (b, c, area)
+        };
+
+        let min_x = a.x.min(b.x).min(c.x).floor().max(0.0) as u32;
+        let min_y = a.y.min(b.y).min(c.y).floor().max(0.0) as u32;
+        let max_x = (a.x.max(b.x).max(c.x).ceil() as u32).min(self.frame.width());
+        let max_y = (a.y.max(b.y).max(c.y).ceil() as u32).min(self.frame.height());
+
+        for y in min_y..max_y {
+            for x in min_x..max_x {
+                let px = x as f64 + 0.5;
+                let py = y as f64 + 0.5;
+
+                let w0 = edge(&b, &c, px, py);
+                let w1 = edge(&c, &a, px, py);
+                let w2 = edge(&a, &b, px, py);
+
+                if !(covers(w0, &b, &c) && covers(w1, &c, &a) && covers(w2, &a, &b)) {
+                    continue;
+                }
+
+                let (w0, w1, w2) = (w0 / area, w1 / area, w2 / area);
+
+                let src = match texture {
+                    None => color,
+                    Some(texture) => {
+                        let u = a.u * w0 + b.u * w1 + c.u * w2;
+                        let v = a.v * w0 + b.v * w1 + c.v * w2;
+                        let texel = Self::sample(&self.textures[texture as usize], u, v);
+
+                        [
+                            texel[0] * color[0],
+                            texel[1] * color[1],
+                            texel[2] * color[2],
+                            texel[3] * color[3],
+                        ]
+                    }
+                };
+
+                self.blend(x, y, src);
+            }
+        }
+    }
+}
+
+impl Default for SoftwareRenderer {
+    fn default() -> Self {
+        Self::new(1920, 1080)
+    }
+}
+
+impl Renderer for SoftwareRenderer {
+    fn create_shader(&mut self, _vert_src: &str, _frag_src: &str) -> Result<i32, String> {
+        self.shaders.push(ShaderState {
+            transform: Transform::new([0.0, 0.0].into(), [0.0, 0.0].into(), 0.0),
+            camera_position: [0.0, 0.0],
+            camera_zoom: 1.0,
+            color: [1.0, 1.0, 1.0, 1.0],
+        });
+        Ok(self.shaders.len() as i32 - 1)
+    }
+
+    fn create_texture(&mut self, image: &RgbaImage) -> i32 {
+        self.textures.push(image.clone());
+        self.textures.len() as i32 - 1
+    }
+
+    fn create_mesh(&mut self, vertices: &[f32], layout: VertexLayout) -> i32 {
+        self.meshes.push(SoftwareMesh {
+            vertices: vertices.to_vec(),
+            layout,
+        });
+        self.meshes.len() as i32 - 1
+    }
+
+    fn update_mesh(&mut self, mesh: i32, vertices: &[f32]) {
+        self.meshes[mesh as usize].vertices = vertices.to_vec();
+    }
+
+    fn bind_shader(&mut self, shader: i32) {
+        self.curr_shader = shader;
+    }
+
+    fn bind_texture(&mut self, texture: i32) {
+        self.curr_texture = Some(texture);
+    }
+
+    fn uniform(&mut self, shader: i32, name: &str, value: Uniform) {
+        if let ("fragColor", Uniform::Vec4(color)) = (name, value) {
+            self.shaders[shader as usize].color = color;
+        }
+    }
+
+    fn uniform_transform(&mut self, shader: i32, transform: &Transform) {
+        self.shaders[shader as usize].transform = transform.clone();
+    }
+
+    fn uniform_camera(&mut self, shader: i32, camera: &Camera) {
+        let shader = &mut self.shaders[shader as usize];
+        shader.camera_position = camera.position.as_arr();
+        shader.camera_zoom = camera.zoom;
+    }
+
+    fn draw_mesh(&mut self, mesh: i32) {
+        let shader = &self.shaders[self.curr_shader as usize];
+        let mesh = &self.meshes[mesh as usize];
+        let stride = mesh.layout.floats_per_vertex();
+
+        let texture = match mesh.layout {
+            VertexLayout::Position => None,
+            _ => self.curr_texture,
+        };
+
+        let (width, height) = (self.frame.width() as f64, self.frame.height() as f64);
+
+        let fragments: Vec<(Fragment, [f32; 4])> = mesh
+            .vertices
+            .chunks_exact(stride)
+            .map(|vertex| {
+                let (x, y) = match mesh.layout {
+                    VertexLayout::Sprite => {
+                        Self::sprite_to_ndc(shader, vertex[0] as f64, vertex[1] as f64)
+                    }
+                    _ => Self::vertex_to_ndc(shader, vertex[0] as f64, vertex[1] as f64),
+                };
+                let (u, v) = match mesh.layout {
+                    VertexLayout::Position => (0.0, 0.0),
+                    _ => (vertex[2] as f64, 1.0 - vertex[3] as f64),
+                };
+                let color = match mesh.layout {
+                    VertexLayout::Sprite => [vertex[4], vertex[5], vertex[6], vertex[7]],
+                    _ => shader.color,
+                };
+
+                let fragment = Fragment {
+                    x: (x + 1.0) / 2.0 * width,
+                    y: (1.0 - y) / 2.0 * height,
+                    u,
+                    v,
+                };
+                (fragment, color)
+            })
+            .collect();
+
+        // Colors are per draw, or per quad for sprites, so one corner's
+        // color stands for the whole triangle.
+        for tri in fragments.chunks_exact(3) {
+            self.rasterize_triangle([tri[0].0, tri[1].0, tri[2].0], tri[0].1, texture);
+        }
+    }
+
+    fn clear(&mut self, color: [f32; 4]) {
+        let color = Rgba(color.map(|c| (c.clamp(0.0, 1.0) * 255.0).round() as u8));
+
+        for pixel in self.frame.pixels_mut() {
+            *pixel = color;
+        }
+    }
+
+    fn frame(&self) -> Option<RgbaImage> {
+        Some(self.frame.clone())
+    }
+}
+
+fn edge(a: &Fragment, b: &Fragment, x: f64, y: f64) -> f64 {
+    (b.x - a.x) * (y - a.y) - (b.y - a.y) * (x - a.x)
+}
+
+/// Top-left fill rule, so pixels on an edge shared by two triangles are only
+/// drawn once.
+fn covers(w: f64, from: &Fragment, to: &Fragment) -> bool {
+    if w != 0.0 {
+        return w > 0.0;
+    }
+
+    let dy = to.y - from.y;
+    let dx = to.x - from.x;
+
+    dy > 0.0 || (dy == 0.0 && dx < 0.0)
+}
@@ -0,0 +1,139 @@
+use crate::engine::web;
+
+/// Everything needed to put an `Rng` back exactly where it was.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct RngState(pub u64);
+
+/// A small seedable generator (SplitMix64). The same seed always gives the
+/// same sequence, so anything drawn from it can be replayed.
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    /// Seeds from the current time, for when no particular sequence is wanted.
+    pub fn from_time() -> Self {
+        Self::new(web::now().to_bits())
+    }
+
+    pub fn reseed(&mut self, seed: u64) {
+        self.state = seed;
+    }
+
+    pub fn snapshot(&self) -> RngState {
+        RngState(self.state)
+    }
+
+    pub fn restore(&mut self, state: RngState) {
+        self.state = state.0;
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e3779b97f4a7c15);
+
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+        z ^ (z >> 31)
+    }
+
+    /// A float in `[0, 1)`.
+    pub fn float(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    /// A float in `[min, max)`.
+    pub fn float_range(&mut self, min: f64, max: f64) -> f64 {
+        min + self.float() * (max - min)
+    }
+
+    /// An integer in `[min, max)`. Returns `min` if the range is empty.
+    pub fn range(&mut self, min: i64, max: i64) -> i64 {
+        if max <= min {
+            return min;
+        }
+
+        let span = max.abs_diff(min);
+        min.wrapping_add(self.below(span) as i64)
+    }
+
+    /// An index in `[0, len)`. `len` must not be 0.
+    pub fn index(&mut self, len: usize) -> usize {
+        self.below(len as u64) as usize
+    }
+
+    /// `true` with probability `p`.
+    pub fn chance(&mut self, p: f64) -> bool {
+        self.float() < p
+    }
+
+    pub fn choose<'a, T>(&mut self, items: &'a [T]) -> Option<&'a T> {
+        if items.is_empty() {
+            return None;
+        }
+
+        Some(&items[self.index(items.len())])
+    }
+
+    /// Fisher-Yates shuffle in place.
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            let j = self.index(i + 1);
+            items.swap(i, j);
+        }
+    }
+
+    /// Picks an index with probability proportional to its weight. Negative
+    /// weights count as 0. Returns `None` if no weight is positive.
+    pub fn weighted_index(&mut self, weights: &[f64]) -> Option<usize> {
+        let total: f64 = weights.iter().map(|w| w.max(0.0)).sum();
+        if total <= 0.0 {
+            return None;
+        }
+
+        let mut target = self.float() * total;
+        let mut last = None;
+        for (i, weight) in weights.iter().enumerate() {
+            if *weight <= 0.0 {
+                continue;
+            }
+
+            if target < *weight {
+                return Some(i);
+            }
+
+            target -= weight;
+            last = Some(i);
+        }
+
+        // Rounding can leave `target` just past the final weight.
+        last
+    }
+
+    /// Picks from `items` weighted by the matching entry in `weights`.
+    pub fn choose_weighted<'a, T>(&mut self, items: &'a [T], weights: &[f64]) -> Option<&'a T> {
+        let len = items.len().min(weights.len());
+        self.weighted_index(&weights[..len]).map(|i| &items[i])
+    }
+
+    // Unbiased integer in `[0, bound)` by rejecting the uneven tail.
+    fn below(&mut self, bound: u64) -> u64 {
+        let zone = u64::MAX - u64::MAX % bound;
+        loop {
+            let value = self.next_u64();
+            if value < zone {
+                return value % bound;
+            }
+        }
+    }
+}
+
+impl Default for Rng {
+    fn default() -> Self {
+        Self::from_time()
+    }
+}
@@ -0,0 +1,489 @@
+use crate::engine::collision::{Manifold, SweepHit};
+use crate::engine::transform::Transform;
+use crate::engine::vec2f::Vec2f;
+
+/// How close `Collider::sweep` has to get to count as touching.
+const SWEEP_TOLERANCE: f64 = 1e-6;
+
+/// How many steps `Collider::sweep` takes towards a shape before giving up
+/// on reaching it.
+const MAX_SWEEP_STEPS: usize = 32;
+
+/// The outline an object collides with, in the same local space as its
+/// mesh: points are scaled by the transform's size, then rotated and moved
+/// to its position. Radii are scaled by the smaller side of the size, so a
+/// circle of radius 0.5 fits the object's rectangle.
+#[derive(Clone, PartialEq, Debug)]
+pub enum Shape {
+    /// The unit square, filling the transform's size.
+    Box,
+    Circle {
+        radius: f64,
+    },
+    /// A convex polygon. The points must go around its outline in order,
+    /// either way round. They aren't checked: a concave or jumbled outline
+    /// collides unpredictably.
+    Polygon {
+        points: Vec<Vec2f>,
+    },
+    /// Two half circles of `radius` joined by straight sides, centered
+    /// `half_length` either side of the origin along the x axis.
+    Capsule {
+        half_length: f64,
+        radius: f64,
+    },
+}
+
+impl Shape {
+    /// The shape placed in the world by `transform`.
+    pub fn collider(&self, transform: &Transform) -> Collider {
+        let scale = transform.size.x.abs().min(transform.size.y.abs());
+
+        let (points, radius) = match self {
+            Shape::Box => {
+                return Collider::new(transform.vertices(), 0.0);
+            }
+            Shape::Circle { radius } => (vec![Vec2f::new(0.0, 0.0)], radius * scale),
+            // An empty polygon is just the position.
+            Shape::Polygon { points } if points.is_empty() => (vec![Vec2f::new(0.0, 0.0)], 0.0),
+            Shape::Polygon { points } => (points.clone(), 0.0),
+            Shape::Capsule {
+                half_length,
+                radius,
+            } => (
+                vec![Vec2f::new(-half_length, 0.0), Vec2f::new(*half_length, 0.0)],
+                radius * scale,
+            ),
+        };
+
+        let points = points
+            .into_iter()
+            .map(|mut point| {
+                point *= &transform.size;
+                &point.rotated(transform.rotation) + &transform.position
+            })
+            .collect();
+
+        Collider::new(points, radius)
+    }
+}
+
+/// A shape in world space: the convex hull of `points`, grown by `radius`.
+/// Every `Shape` places as one. Boxes and polygons have no radius, a circle
+/// is one point and a capsule is two. A collider without points touches
+/// nothing.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Collider {
+    pub points: Vec<Vec2f>,
+    pub radius: f64,
+}
+
+impl Collider {
+    pub fn new(points: Vec<Vec2f>, radius: f64) -> Self {
+        Self { points, radius }
+    }
+
+    pub fn circle(center: Vec2f, radius: f64) -> Self {
+        Self::new(vec![center], radius)
+    }
+
+    /// The rotated rectangle of `transform`.
+    pub fn rect(transform: &Transform) -> Self {
+        Shape::Box.collider(transform)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.points.is_empty()
+    }
+
+    /// The average of the points.
+    pub fn center(&self) -> Vec2f {
+        let mut sum = Vec2f::new(0.0, 0.0);
+        for point in &self.points {
+            sum += point;
+        }
+
+        &sum / self.points.len().max(1) as f64
+    }
+
+    /// The minimum and maximum corners of the axis-aligned box around the
+    /// shape.
+    pub fn bounding_box(&self) -> (Vec2f, Vec2f) {
+        let mut min = Vec2f::new(f64::INFINITY, f64::INFINITY);
+        let mut max = Vec2f::new(f64::NEG_INFINITY, f64::NEG_INFINITY);
+
+        for point in &self.points {
+            min.x = min.x.min(point.x);
+            min.y = min.y.min(point.y);
+            max.x = max.x.max(point.x);
+            max.y = max.y.max(point.y);
+        }
+
+        (
+            Vec2f::new(min.x - self.radius, min.y - self.radius),
+            Vec2f::new(max.x + self.radius, max.y + self.radius),
+        )
+    }
+
+    pub fn translated(&self, offset: &Vec2f) -> Self {
+        Self::new(
+            self.points.iter().map(|point| point + offset).collect(),
+            self.radius,
+        )
+    }
+
+    pub fn contains_point(&self, point: &Vec2f) -> bool {
+        self.hull_contains(point)
+            || self.edges().into_iter().any(|(start, end)| {
+                (point - &Self::closest_on_segment(point, start, end)).len() <= self.radius
+            })
+    }
+
+    pub fn overlaps(&self, other: &Collider) -> bool {
+        if self.is_empty() || other.is_empty() {
+            return false;
+        }
+
+        match self.closest_points(other) {
+            None => true,
+            Some((a, b)) => (&b - &a).len() <= self.radius + other.radius,
+        }
+    }
+
+    /// How far the shapes interpenetrate, if they touch. The normal points
+    /// from `self` towards `other`.
+    pub fn collide(&self, other: &Collider) -> Option<Manifold> {
+        if self.is_empty() || other.is_empty() {
+            return None;
+        }
+
+        let radii = self.radius + other.radius;
+
+        // Shapes whose points are apart meet between their nearest points.
+        if let Some((a, b)) = self.closest_points(other) {
+            let offset = &b - &a;
+            let distance = offset.len();
+            if distance > radii {
+                return None;
+            }
+
+            let normal = &offset / distance;
+            let depth = radii - distance;
+            let point = &a + &(&normal * (self.radius - depth / 2.0));
+
+            return Some(Manifold {
+                normal,
+                depth,
+                points: vec![point],
+            });
+        }
+
+        // Otherwise, separating axis test over the edges of both, pushing
+        // `other` whichever way along an axis gets it out soonest.
+        let mut best: Option<(Vec2f, f64)> = None;
+        let mut axes = self.axes();
+        axes.extend(other.axes());
+
+        for axis in axes {
+            let (min1, max1) = self.project(&axis);
+            let (min2, max2) = other.project(&axis);
+
+            let (axis, overlap) = if max1 - min2 <= max2 - min1 {
+                (axis, max1 - min2)
+            } else {
+                (&axis * -1.0, max2 - min1)
+            };
+            if best
+                .as_ref()
+                .is_none_or(|(_, best_overlap)| overlap < *best_overlap)
+            {
+                best = Some((axis, overlap));
+            }
+        }
+
+        let (center1, center2) = (self.center(), other.center());
+        let (normal, overlap) = match best {
+            Some(best) => best,
+            // Two single points on top of each other have no edges.
+            None => (Vec2f::new(0.0, 1.0), 0.0),
+        };
+
+        let mut points: Vec<Vec2f> = self
+            .points
+            .iter()
+            .filter(|point| other.contains_point(point))
+            .cloned()
+            .collect();
+        points.extend(
+            other
+                .points
+                .iter()
+                .filter(|point| self.contains_point(point))
+                .cloned(),
+        );
+        if points.is_empty() {
+            points.push(&(&center1 + &center2) / 2.0);
+        }
+
+        Some(Manifold {
+            normal,
+            depth: overlap + radii,
+            points,
+        })
+    }
+
+    /// How far `self` travels along the unit vector `direction`, up to
+    /// `max_distance`, before touching `other`, and the outward normal of the
+    /// surface it touches. A shape already overlapping `other` touches it at
+    /// 0, with the normal facing back along `direction`.
+    pub fn cast(
+        &self,
+        direction: &Vec2f,
+        max_distance: f64,
+        other: &Collider,
+    ) -> Option<(f64, Vec2f)> {
+        if self.overlaps(other) {
+            return Some((0.0, direction * -1.0));
+        }
+
+        let (time, normal) = self.sweep_one(&(direction * max_distance), other)?;
+        Some((time * max_distance, &normal * -1.0))
+    }
+
+    /// The first of `others` that `self` runs into when moved by
+    /// `displacement`, without rotating. Shapes already overlapping only
+    /// count if the movement goes deeper into them, hitting at time 0.
+    pub fn sweep(&self, displacement: &Vec2f, others: &[Collider]) -> Option<SweepHit> {
+        let mut first: Option<SweepHit> = None;
+
+        for (index, other) in others.iter().enumerate() {
+            if let Some((time, normal)) = self.sweep_one(displacement, other) {
+                if first.as_ref().is_none_or(|first| time < first.time) {
+                    first = Some(SweepHit {
+                        index,
+                        time,
+                        normal,
+                    });
+                }
+            }
+        }
+
+        first
+    }
+
+    // Conservative advancement: the gap between convex shapes can't close
+    // faster than the movement along the line between their nearest points,
+    // so stepping that far never steps through.
+    fn sweep_one(&self, displacement: &Vec2f, other: &Collider) -> Option<(f64, Vec2f)> {
+        if self.is_empty() || other.is_empty() {
+            return None;
+        }
+
+        if let Some(manifold) = self.collide(other) {
+            return if displacement.dot(&manifold.normal) > 0.0 {
+                Some((0.0, manifold.normal))
+            } else {
+                None
+            };
+        }
+
+        let radii = self.radius + other.radius;
+        let mut time = 0.0;
+
+        for _ in 0..MAX_SWEEP_STEPS {
+            let moved = self.translated(&(displacement * time));
+            let (a, b) = match moved.closest_points(other) {
+                Some(points) => points,
+                None => return Some((time, moved.separating_axis(other)?)),
+            };
+
+            let offset = &b - &a;
+            let distance = offset.len();
+
+            let gap = distance - radii;
+            if gap <= SWEEP_TOLERANCE {
+                // Hulls this close have no direction between them worth
+                // trusting, so the side they touch with gives the normal.
+                let normal = if distance > SWEEP_TOLERANCE {
+                    &offset / distance
+                } else {
+                    moved.separating_axis(other)?
+                };
+
+                return (displacement.dot(&normal) > 0.0).then_some((time, normal));
+            }
+
+            let closing = displacement.dot(&(&offset / distance));
+            if closing <= 0.0 {
+                return None;
+            }
+
+            time += gap / closing;
+            if time > 1.0 {
+                return None;
+            }
+        }
+
+        // Never got close enough to touch.
+        None
+    }
+
+    // The axis of either hull's edges along which the hulls are furthest
+    // apart, pointing from `self` towards `other`. For hulls that touch, it
+    // is the normal of the side they touch with. `None` if neither has an
+    // edge.
+    fn separating_axis(&self, other: &Collider) -> Option<Vec2f> {
+        let mut best: Option<(Vec2f, f64)> = None;
+        let mut axes = self.axes();
+        axes.extend(other.axes());
+
+        for axis in axes {
+            let (min1, max1) = self.project(&axis);
+            let (min2, max2) = other.project(&axis);
+
+            let (axis, separation) = if min2 - max1 >= min1 - max2 {
+                (axis, min2 - max1)
+            } else {
+                (&axis * -1.0, min1 - max2)
+            };
+            if best
+                .as_ref()
+                .is_none_or(|(_, best_separation)| separation > *best_separation)
+            {
+                best = Some((axis, separation));
+            }
+        }
+
+        best.map(|(axis, _)| axis)
+    }
+
+    // The nearest points between the two hulls, ignoring radii, or `None`
+    // if the hulls touch. Neither may be empty.
+    fn closest_points(&self, other: &Collider) -> Option<(Vec2f, Vec2f)> {
+        let mut best: Option<(Vec2f, Vec2f)> = None;
+        let mut best_distance = f64::INFINITY;
+
+        for (a1, a2) in self.edges() {
+            for (b1, b2) in other.edges() {
+                let (a, b) = Self::closest_between_segments(a1, a2, b1, b2)?;
+                let distance = (&b - &a).len();
+                if distance < best_distance {
+                    best_distance = distance;
+                    best = Some((a, b));
+                }
+            }
+        }
+
+        // One hull inside the other crosses no edges.
+        if self.hull_contains(&other.points[0]) || other.hull_contains(&self.points[0]) {
+            return None;
+        }
+
+        best.filter(|_| best_distance > 0.0)
+    }
+
+    fn hull_contains(&self, point: &Vec2f) -> bool {
+        if self.points.len() < 3 {
+            return false;
+        }
+
+        let mut sign = 0.0;
+        for (a, b) in self.edges() {
+            let side = cross(&(b - a), &(point - a));
+            if side * sign < 0.0 {
+                return false;
+            }
+            if side != 0.0 {
+                sign = side;
+            }
+        }
+
+        true
+    }
+
+    // Consecutive points, closing the loop. A single point is an edge to
+    // itself.
+    fn edges(&self) -> Vec<(&Vec2f, &Vec2f)> {
+        match self.points.len() {
+            0 => Vec::new(),
+            1 => vec![(&self.points[0], &self.points[0])],
+            2 => vec![(&self.points[0], &self.points[1])],
+            count => (0..count)
+                .map(|i| (&self.points[i], &self.points[(i + 1) % count]))
+                .collect(),
+        }
+    }
+
+    // Unit normals of the edges. A lone segment also separates along its
+    // own direction, past its ends.
+    fn axes(&self) -> Vec<Vec2f> {
+        let mut axes: Vec<Vec2f> = Vec::new();
+
+        for (a, b) in self.edges() {
+            let edge = b - a;
+            if edge.len() == 0.0 {
+                continue;
+            }
+
+            axes.push(Vec2f::new(edge.y, -edge.x).normalize());
+            if self.points.len() == 2 {
+                axes.push(edge.normalize());
+            }
+        }
+
+        axes
+    }
+
+    fn project(&self, axis: &Vec2f) -> (f64, f64) {
+        let mut min = f64::INFINITY;
+        let mut max = f64::NEG_INFINITY;
+
+        for point in &self.points {
+            let projection = point.dot(axis);
+            min = min.min(projection);
+            max = max.max(projection);
+        }
+
+        (min, max)
+    }
+
+    // The nearest points between two segments, or `None` if they cross.
+    fn closest_between_segments(
+        a1: &Vec2f,
+        a2: &Vec2f,
+        b1: &Vec2f,
+        b2: &Vec2f,
+    ) -> Option<(Vec2f, Vec2f)> {
+        let a_sides = (cross(&(b2 - b1), &(a1 - b1)), cross(&(b2 - b1), &(a2 - b1)));
+        let b_sides = (cross(&(a2 - a1), &(b1 - a1)), cross(&(a2 - a1), &(b2 - a1)));
+        if a_sides.0 * a_sides.1 < 0.0 && b_sides.0 * b_sides.1 < 0.0 {
+            return None;
+        }
+
+        let candidates = [
+            (a1.clone(), Self::closest_on_segment(a1, b1, b2)),
+            (a2.clone(), Self::closest_on_segment(a2, b1, b2)),
+            (Self::closest_on_segment(b1, a1, a2), b1.clone()),
+            (Self::closest_on_segment(b2, a1, a2), b2.clone()),
+        ];
+
+        candidates
+            .into_iter()
+            .min_by(|(a1, b1), (a2, b2)| (b1 - a1).len().total_cmp(&(b2 - a2).len()))
+    }
+
+    fn closest_on_segment(point: &Vec2f, start: &Vec2f, end: &Vec2f) -> Vec2f {
+        let segment = end - start;
+        let length_squared = segment.dot(&segment);
+        if length_squared == 0.0 {
+            return start.clone();
+        }
+
+        let t = ((point - start).dot(&segment) / length_squared).clamp(0.0, 1.0);
+        start + &(&segment * t)
+    }
+}
+
+fn cross(a: &Vec2f, b: &Vec2f) -> f64 {
+    a.x * b.y - a.y * b.x
+}
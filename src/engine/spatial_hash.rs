@@ -0,0 +1,158 @@
+use crate::engine::object_manager::ObjectId;
+use crate::engine::vec2f::Vec2f;
+use std::collections::HashMap;
+
+/// Objects spanning more cells than this are kept in a list that every query
+/// checks, rather than being added to each cell.
+const MAX_CELLS_PER_OBJECT: i128 = 64;
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+struct CellRange {
+    min: (i64, i64),
+    max: (i64, i64),
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+enum Entry {
+    Cells(CellRange),
+    Large,
+}
+
+/// A uniform grid over world space for finding the objects near a box
+/// without looking at every object. It only narrows things down: callers
+/// still test each candidate against its current transform.
+pub struct SpatialHash {
+    cell_size: f64,
+    cells: HashMap<(i64, i64), Vec<ObjectId>>,
+    entries: HashMap<ObjectId, Entry>,
+    large: Vec<ObjectId>,
+}
+
+impl SpatialHash {
+    pub fn new(cell_size: f64) -> Self {
+        Self {
+            cell_size,
+            cells: HashMap::new(),
+            entries: HashMap::new(),
+            large: Vec::new(),
+        }
+    }
+
+    /// Files `id` under the cells the box from `min` to `max` covers, moving
+    /// it if it was already filed elsewhere. `None` removes it.
+    pub fn update(&mut self, id: ObjectId, bounds: Option<(Vec2f, Vec2f)>) {
+        let entry = match bounds {
+            None => {
+                self.remove(id);
+                return;
+            }
+            Some((min, max)) => self.entry(&min, &max),
+        };
+
+        if self.entries.get(&id) == Some(&entry) {
+            return;
+        }
+
+        self.remove(id);
+
+        match entry {
+            Entry::Large => self.large.push(id),
+            Entry::Cells(range) => {
+                for x in range.min.0..=range.max.0 {
+                    for y in range.min.1..=range.max.1 {
+                        self.cells.entry((x, y)).or_default().push(id);
+                    }
+                }
+            }
+        }
+
+        self.entries.insert(id, entry);
+    }
+
+    pub fn remove(&mut self, id: ObjectId) {
+        match self.entries.remove(&id) {
+            None => {}
+            Some(Entry::Large) => self.large.retain(|other| *other != id),
+            Some(Entry::Cells(range)) => {
+                for x in range.min.0..=range.max.0 {
+                    for y in range.min.1..=range.max.1 {
+                        if let Some(cell) = self.cells.get_mut(&(x, y)) {
+                            cell.retain(|other| *other != id);
+                            if cell.is_empty() {
+                                self.cells.remove(&(x, y));
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.cells.clear();
+        self.entries.clear();
+        self.large.clear();
+    }
+
+    /// Every object filed under a cell the box from `min` to `max` touches,
+    /// each once, in id order.
+    pub fn query(&self, min: &Vec2f, max: &Vec2f) -> Vec<ObjectId> {
+        let mut found = self.large.clone();
+        let range = self.range(min, max);
+
+        // Past a point, walking the occupied cells beats walking the box.
+        if range.cell_count() > self.cells.len() as i128 {
+            for (cell, ids) in &self.cells {
+                if range.contains(*cell) {
+                    found.extend(ids);
+                }
+            }
+        } else {
+            for x in range.min.0..=range.max.0 {
+                for y in range.min.1..=range.max.1 {
+                    if let Some(ids) = self.cells.get(&(x, y)) {
+                        found.extend(ids);
+                    }
+                }
+            }
+        }
+
+        found.sort_unstable();
+        found.dedup();
+        found
+    }
+
+    fn entry(&self, min: &Vec2f, max: &Vec2f) -> Entry {
+        let range = self.range(min, max);
+        if range.cell_count() > MAX_CELLS_PER_OBJECT {
+            return Entry::Large;
+        }
+
+        Entry::Cells(range)
+    }
+
+    fn range(&self, min: &Vec2f, max: &Vec2f) -> CellRange {
+        CellRange {
+            min: (self.cell(min.x), self.cell(min.y)),
+            max: (self.cell(max.x), self.cell(max.y)),
+        }
+    }
+
+    // Saturates for infinite coordinates, and puts NaN in cell 0, where the
+    // caller's exact test rejects it anyway.
+    fn cell(&self, coordinate: f64) -> i64 {
+        (coordinate / self.cell_size).floor() as i64
+    }
+}
+
+impl CellRange {
+    fn cell_count(&self) -> i128 {
+        let width = (self.max.0 as i128 - self.min.0 as i128 + 1).max(0);
+        let height = (self.max.1 as i128 - self.min.1 as i128 + 1).max(0);
+        width * height
+    }
+
+    fn contains(&self, cell: (i64, i64)) -> bool {
+        (self.min.0..=self.max.0).contains(&cell.0) && (self.min.1..=self.max.1).contains(&cell.1)
+    }
+}
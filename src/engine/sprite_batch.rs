@@ -0,0 +1,165 @@
+use crate::engine::camera::Camera;
+use crate::engine::renderer::{Renderer, VertexLayout};
+use crate::engine::shader::Shader;
+use crate::engine::texture::Texture;
+use crate::engine::transform::Transform;
+use image::{Rgba, RgbaImage};
+
+const HALF_SCREEN_WIDTH: f64 = 960.0;
+const HALF_SCREEN_HEIGHT: f64 = 540.0;
+
+/// Most quads drawn in one call. Past this the batch flushes early.
+const MAX_SPRITES: usize = 4096;
+
+const FLOATS_PER_SPRITE: usize = 6 * 8;
+
+/// The whole of a texture, as a UV rect.
+pub const FULL_UV: [f32; 4] = [0.0, 0.0, 1.0, 1.0];
+
+/// Corners of the unit quad as two triangles, with how far across the UV
+/// rect each one is.
+#[rustfmt::skip]
+const QUAD: [(f64, f64, f32, f32); 6] = [
+    (-0.5, -0.5,  0.0, 0.0),
+    ( 0.5, -0.5,  1.0, 0.0),
+    (-0.5,  0.5,  0.0, 1.0),
+    ( 0.5, -0.5,  1.0, 0.0),
+    ( 0.5,  0.5,  1.0, 1.0),
+    (-0.5,  0.5,  0.0, 1.0),
+];
+
+/// One quad queued in a `SpriteBatch`.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Sprite {
+    pub transform: Transform,
+    pub color: [f32; 4],
+    /// The part of the texture shown, as `[u0, v0, u1, v1]`.
+    pub uv: [f32; 4],
+}
+
+impl Sprite {
+    /// The quad as two triangles in `VertexLayout::Sprite`, placed on the
+    /// CPU the same way `colored_vert.glsl` places a rect, leaving only the
+    /// camera to the shader.
+    pub fn vertices(&self) -> Vec<f32> {
+        let transform = &self.transform;
+        let (sin, cos) = transform.rotation.sin_cos();
+        let position = [
+            transform.position.x / HALF_SCREEN_WIDTH,
+            transform.position.y / HALF_SCREEN_HEIGHT,
+        ];
+
+        let mut vertices = Vec::with_capacity(FLOATS_PER_SPRITE);
+        for (x, y, u, v) in QUAD {
+            let x = x * transform.size.x / HALF_SCREEN_WIDTH;
+            let y = y * transform.size.y / HALF_SCREEN_WIDTH;
+
+            let x_rotated = x * cos - y * sin;
+            let y_rotated = (y * cos + x * sin) * 16.0 / 9.0;
+
+            vertices.extend_from_slice(&[
+                (x_rotated + position[0]) as f32,
+                (y_rotated + position[1]) as f32,
+                self.uv[0] + (self.uv[2] - self.uv[0]) * u,
+                self.uv[1] + (self.uv[3] - self.uv[1]) * v,
+                self.color[0],
+                self.color[1],
+                self.color[2],
+                self.color[3],
+            ]);
+        }
+
+        vertices
+    }
+}
+
+/// Collects quads and draws them in a single call through
+/// `Renderer::draw_sprites`, starting a new one whenever the texture changes.
+/// Plain colored quads sample a white texture, so they batch with each other.
+pub struct SpriteBatch {
+    shader: Shader,
+    mesh: i32,
+    white: Texture,
+    sprites: Vec<Sprite>,
+    texture: i32,
+    draw_calls: u32,
+}
+
+impl SpriteBatch {
+    pub fn new(renderer: &mut dyn Renderer) -> Self {
+        let shader = Shader::from_source(
+            renderer,
+            include_str!("../../public/shaders/sprite_vert.glsl"),
+            include_str!("../../public/shaders/sprite_frag.glsl"),
+        )
+        .expect("Should be able to create sprite shader");
+
+        let mesh = renderer.create_mesh(&[], VertexLayout::Sprite);
+        let white = Texture::from_image(renderer, &RgbaImage::from_pixel(1, 1, Rgba([255; 4])));
+
+        Self {
+            shader,
+            mesh,
+            texture: white.id(),
+            white,
+            sprites: Vec::new(),
+            draw_calls: 0,
+        }
+    }
+
+    /// Queues a quad filling `transform`, showing the part of `texture`
+    /// between the corners `[u0, v0, u1, v1]` of `uv`, tinted by `color`.
+    /// Without a texture the quad is plain `color`.
+    pub fn draw(
+        &mut self,
+        renderer: &mut dyn Renderer,
+        transform: &Transform,
+        color: [f32; 4],
+        texture: Option<&Texture>,
+        uv: [f32; 4],
+    ) {
+        let texture = texture.unwrap_or(&self.white).id();
+        if texture != self.texture || self.sprites.len() >= MAX_SPRITES {
+            self.flush(renderer);
+            self.texture = texture;
+        }
+
+        self.sprites.push(Sprite {
+            transform: transform.clone(),
+            color,
+            uv,
+        });
+    }
+
+    /// Draws everything queued, leaving the batch's shader bound. Returns
+    /// whether there was anything to draw.
+    pub fn flush(&mut self, renderer: &mut dyn Renderer) -> bool {
+        if self.sprites.is_empty() {
+            return false;
+        }
+
+        self.shader.bind(renderer);
+        self.shader.uniform1i(renderer, "image", 0);
+        renderer.bind_texture(self.texture);
+        renderer.draw_sprites(self.mesh, &self.sprites);
+
+        self.sprites.clear();
+        self.draw_calls += 1;
+
+        true
+    }
+
+    pub fn update_camera(&self, renderer: &mut dyn Renderer, camera: &Camera) {
+        self.shader.bind(renderer);
+        self.shader.uniform_camera(renderer, camera);
+    }
+
+    pub fn shader(&self) -> &Shader {
+        &self.shader
+    }
+
+    /// How many draw calls the batch has made since this was last called.
+    pub fn take_draw_calls(&mut self) -> u32 {
+        std::mem::take(&mut self.draw_calls)
+    }
+}
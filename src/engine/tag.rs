@@ -0,0 +1,66 @@
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::fmt;
+
+thread_local! {
+    static INTERNER: RefCell<Interner> = RefCell::new(Interner::new());
+}
+
+struct Interner {
+    ids: HashMap<&'static str, u32>,
+    names: Vec<&'static str>,
+}
+
+impl Interner {
+    fn new() -> Self {
+        Self {
+            ids: HashMap::new(),
+            names: Vec::new(),
+        }
+    }
+}
+
+/// An interned tag name. Comparing and hashing tags is comparing integers,
+/// and making one from a name that has been seen before doesn't allocate.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Tag(u32);
+
+impl Tag {
+    pub fn new(name: &str) -> Self {
+        INTERNER.with(|interner| {
+            let mut interner = interner.borrow_mut();
+            if let Some(id) = interner.ids.get(name) {
+                return Tag(*id);
+            }
+
+            // Names live as long as the program, like the tags made from them.
+            let name: &'static str = Box::leak(name.to_string().into_boxed_str());
+            let id = interner.names.len() as u32;
+            interner.names.push(name);
+            interner.ids.insert(name, id);
+            Tag(id)
+        })
+    }
+
+    pub fn name(self) -> &'static str {
+        INTERNER.with(|interner| interner.borrow().names[self.0 as usize])
+    }
+}
+
+impl From<&str> for Tag {
+    fn from(name: &str) -> Self {
+        Tag::new(name)
+    }
+}
+
+impl fmt::Display for Tag {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl fmt::Debug for Tag {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Tag({:?})", self.name())
+    }
+}
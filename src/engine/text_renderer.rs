@@ -1,43 +1,49 @@
 use crate::engine::vec2f::Vec2f;
-use wasm_bindgen::prelude::wasm_bindgen;
-
-#[wasm_bindgen(module = "/web.js")]
-extern "C" {
-    fn makeTextCanvas(text: String, width: f64, height: f64) -> web_sys::CanvasRenderingContext2d;
-    fn changeTextFillStyle(style: String);
-}
-
-static mut TR: Option<Box<TextRenderer>> = None;
-
-pub fn tr() -> &'static mut TextRenderer {
-    unsafe {
-        TR.as_deref_mut()
-            .expect("TextRenderer should be initialized")
-    }
-}
+#[cfg(feature = "web")]
+use crate::engine::web::text_canvas::TextCanvas;
 
+/// Draws text onto the overlay canvas. Without a canvas (headless) every call
+/// is a no-op.
 pub struct TextRenderer {
-    pub text_canvas: web_sys::CanvasRenderingContext2d,
+    #[cfg(feature = "web")]
+    text_canvas: Option<TextCanvas>,
 }
 
 impl TextRenderer {
-    pub async fn init() {
-        let text_canvas = makeTextCanvas("".into(), 1920.0, 1080.0);
+    #[cfg(feature = "web")]
+    pub async fn new() -> Self {
+        Self {
+            text_canvas: Some(TextCanvas::new(1920.0, 1080.0)),
+        }
+    }
 
-        unsafe { TR = Some(Box::new(Self { text_canvas })) }
+    pub fn new_headless() -> Self {
+        Self {
+            #[cfg(feature = "web")]
+            text_canvas: None,
+        }
     }
 
     pub fn clear(&self) {
-        self.text_canvas.clear_rect(0.0, 0.0, 1920.0, 1080.0);
+        #[cfg(feature = "web")]
+        if let Some(text_canvas) = &self.text_canvas {
+            text_canvas.clear();
+        }
     }
 
+    #[cfg_attr(not(feature = "web"), allow(unused_variables))]
     pub fn draw_text(&self, text: &str, pos: Vec2f) {
-        self.text_canvas
-            .fill_text(text, pos.x, pos.y)
-            .expect("Should be able to draw text.");
+        #[cfg(feature = "web")]
+        if let Some(text_canvas) = &self.text_canvas {
+            text_canvas.fill_text(text, pos);
+        }
     }
-    
+
+    #[cfg_attr(not(feature = "web"), allow(unused_variables))]
     pub fn set_text_color(&self, color: String) {
-        changeTextFillStyle(color);
+        #[cfg(feature = "web")]
+        if let Some(text_canvas) = &self.text_canvas {
+            text_canvas.set_fill_style(color);
+        }
     }
 }
@@ -1,24 +1,40 @@
+use crate::engine::clock::{BrowserClock, Clock};
+
 pub struct Timer {
+    clock: Box<dyn Clock>,
     start_time: f64,
 }
 
 impl Timer {
+    /// Times real time through a `BrowserClock`.
     pub fn new() -> Self {
+        Self::with_clock(BrowserClock)
+    }
+
+    pub fn with_clock(clock: impl Clock + 'static) -> Self {
+        let start_time = clock.now();
+
         Self {
-            start_time: web_sys::window().unwrap().performance().unwrap().now(),
+            clock: Box::new(clock),
+            start_time,
         }
     }
 
     pub fn elapsed_reset(&mut self) -> f64 {
-        let elapsed = web_sys::window().unwrap().performance().unwrap().now() - self.start_time;
-        self.start_time = web_sys::window().unwrap().performance().unwrap().now();
+        let now = self.clock.now();
+        let elapsed = now - self.start_time;
+        self.start_time = now;
 
         elapsed
     }
 
     pub fn elapsed(&self) -> f64 {
-        let elapsed = web_sys::window().unwrap().performance().unwrap().now() - self.start_time;
+        self.clock.now() - self.start_time
+    }
+}
 
-        elapsed
+impl Default for Timer {
+    fn default() -> Self {
+        Self::new()
     }
 }
@@ -1,7 +1,9 @@
+use crate::engine::collision::{Manifold, SweepHit};
 use crate::engine::line_seg::LineSeg;
+use crate::engine::shape::Collider;
 use crate::engine::vec2f::Vec2f;
-use js_sys::Math::{max, min};
 
+#[derive(PartialEq, Debug)]
 pub struct Transform {
     pub position: Vec2f,
     pub size: Vec2f,
@@ -17,6 +19,31 @@ impl Transform {
         }
     }
 
+    /// The transform `t` of the way from `self` to `other`.
+    pub fn lerp(&self, other: &Transform, t: f64) -> Transform {
+        Transform::new(
+            &self.position + &(&(&other.position - &self.position) * t),
+            &self.size + &(&(&other.size - &self.size) * t),
+            self.rotation + (other.rotation - self.rotation) * t,
+        )
+    }
+
+    /// Places `local`, given relative to `self`, into the space `self` is in.
+    /// Offsets and size of `local` are multiplied by `scale` first.
+    pub fn compose(&self, local: &Transform, scale: &Vec2f) -> Transform {
+        let mut offset = local.position.clone();
+        offset *= scale;
+
+        let mut size = local.size.clone();
+        size *= scale;
+
+        Transform::new(
+            &self.position + &offset.rotated(self.rotation),
+            size,
+            self.rotation + local.rotation,
+        )
+    }
+
     pub fn overlaps(&self, other: &Transform) -> bool {
         let vertices1 = self.vertices();
         let vertices2 = other.vertices();
@@ -36,9 +63,36 @@ impl Transform {
         true
     }
 
+    /// Like `overlaps`, but also works out how far the rectangles
+    /// interpenetrate. The normal points from `self` towards `other`.
+    pub fn collide(&self, other: &Transform) -> Option<Manifold> {
+        Collider::rect(self).collide(&Collider::rect(other))
+    }
+
+    /// The first of `others` that `self` runs into when moved by
+    /// `displacement`, without rotating. See `Collider::sweep`.
+    pub fn sweep(&self, displacement: &Vec2f, others: &[Transform]) -> Option<SweepHit> {
+        let others: Vec<Collider> = others.iter().map(Collider::rect).collect();
+
+        Collider::rect(self).sweep(displacement, &others)
+    }
+
+    /// Whether the axis-aligned box around `self` overlaps `other`, taken
+    /// as unrotated.
     pub fn overlaps_lazy(&self, other: &Transform) -> bool {
-        let self_half_size = &self.size.abs() / 2.0;
         let other_half_size = &other.size.abs() / 2.0;
+        let (min, max) = self.bounding_box();
+
+        min.x < other.position.x + other_half_size.x
+            && max.x > other.position.x - other_half_size.x
+            && min.y < other.position.y + other_half_size.y
+            && max.y > other.position.y - other_half_size.y
+    }
+
+    /// The minimum and maximum corners of the axis-aligned box around the
+    /// rotated rectangle.
+    pub fn bounding_box(&self) -> (Vec2f, Vec2f) {
+        let self_half_size = &self.size.abs() / 2.0;
 
         let rotated_corners = [
             Vec2f::new(self_half_size.x, self_half_size.y).rotated(self.rotation),
@@ -68,10 +122,15 @@ impl Transform {
             }
         }
 
-        min_x < other.position.x + other_half_size.x
-            && max_x > other.position.x - other_half_size.x
-            && min_y < other.position.y + other_half_size.y
-            && max_y > other.position.y - other_half_size.y
+        (Vec2f::new(min_x, min_y), Vec2f::new(max_x, max_y))
+    }
+
+    /// Whether `point` lies inside the rotated rectangle.
+    pub fn contains_point(&self, point: &Vec2f) -> bool {
+        let local = (point - &self.position).rotated(-self.rotation);
+        let half_size = &self.size.abs() / 2.0;
+
+        local.x.abs() <= half_size.x && local.y.abs() <= half_size.y
     }
 
     pub fn vertices(&self) -> Vec<Vec2f> {
@@ -149,7 +208,7 @@ impl Transform {
         let ap = p - a;
 
         let t = Vec2f::dot(&ap, &ab) / Vec2f::dot(&ab, &ab);
-        let t = max(0.0, min(1.0, t));
+        let t = t.clamp(0.0, 1.0);
 
         let c = a + &(&ab * t);
 
@@ -1,4 +1,3 @@
-use js_sys::Math::{cos, sin, sqrt};
 use std::ops;
 
 #[derive(PartialEq, Debug)]
@@ -30,7 +29,7 @@ impl Vec2f {
     }
 
     pub fn len(&self) -> f64 {
-        sqrt(self.x * self.x + self.y * self.y)
+        (self.x * self.x + self.y * self.y).sqrt()
     }
 
     pub fn dot(&self, other: &Vec2f) -> f64 {
@@ -39,8 +38,8 @@ impl Vec2f {
 
     pub fn rotated(&mut self, angle: f64) -> Self {
         Self {
-            x: self.x * cos(angle) - self.y * sin(angle),
-            y: self.y * cos(angle) + self.x * sin(angle),
+            x: self.x * angle.cos() - self.y * angle.sin(),
+            y: self.y * angle.cos() + self.x * angle.sin(),
         }
     }
 
@@ -0,0 +1,56 @@
+use js_sys::Uint8Array;
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::JsValue;
+
+#[wasm_bindgen(module = "/web.js")]
+extern "C" {
+    async fn fetchStringFromServer(url: &str) -> JsValue;
+    async fn fetchBytesFromServer(url: &str) -> JsValue;
+}
+
+pub async fn get_string(url: &str) -> Result<String, String> {
+    let result = fetchStringFromServer(url).await;
+    match result.as_string() {
+        Some(s) => Ok(s),
+        None => Err("Failed to fetch string from server".into()),
+    }
+}
+
+pub async fn get_bytes(url: &str) -> Result<Vec<u8>, String> {
+    let result = fetchBytesFromServer(url).await;
+    if let Some(uint8_array) = result.dyn_ref::<Uint8Array>() {
+        Ok(uint8_array.to_vec())
+    } else {
+        Err("Failed to fetch bytes from server".into())
+    }
+}
+
+/// Resolves on the next `requestAnimationFrame` callback with its timestamp in
+/// milliseconds.
+pub async fn next_animation_frame() -> f64 {
+    let promise = js_sys::Promise::new(&mut |resolve, _reject| {
+        web_sys::window()
+            .unwrap()
+            .request_animation_frame(&resolve)
+            .unwrap();
+    });
+
+    wasm_bindgen_futures::JsFuture::from(promise)
+        .await
+        .unwrap()
+        .as_f64()
+        .unwrap()
+}
+
+/// Leaves the game for the arcade menu.
+pub fn exit() {
+    web_sys::window()
+        .unwrap()
+        .open_with_url_and_target("https://lncn.ac/rarcade", "_parent")
+        .expect("Should be able to open arcade menu.");
+}
+
+/// `performance.now()`, in milliseconds.
+pub fn now() -> f64 {
+    web_sys::window().unwrap().performance().unwrap().now()
+}
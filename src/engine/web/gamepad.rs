@@ -0,0 +1,31 @@
+use crate::engine::input::gamepad::GamepadState;
+use wasm_bindgen::JsCast;
+use web_sys::{Gamepad, GamepadButton};
+
+/// Reads every gamepad slot from `navigator.getGamepads()`.
+pub fn snapshot() -> Vec<Option<GamepadState>> {
+    let navigator = web_sys::window().unwrap().navigator();
+    let pads = match navigator.get_gamepads() {
+        Ok(pads) => pads,
+        Err(_) => return Vec::new(),
+    };
+
+    pads.iter()
+        .map(|pad| {
+            let pad = pad.dyn_into::<Gamepad>().ok()?;
+            if !pad.connected() {
+                return None;
+            }
+
+            Some(GamepadState {
+                buttons: pad
+                    .buttons()
+                    .iter()
+                    .filter_map(|button| button.dyn_into::<GamepadButton>().ok())
+                    .map(|button| button.value())
+                    .collect(),
+                axes: pad.axes().iter().filter_map(|axis| axis.as_f64()).collect(),
+            })
+        })
+        .collect()
+}
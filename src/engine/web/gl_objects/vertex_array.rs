@@ -1,5 +1,4 @@
-use crate::engine::gl;
-use crate::engine::gl_objects::vertex_buffer::VertexBuffer;
+use crate::engine::web::gl_objects::vertex_buffer::VertexBuffer;
 use web_sys::{WebGl2RenderingContext, WebGlVertexArrayObject};
 
 pub struct VertexArray {
@@ -7,18 +6,20 @@ pub struct VertexArray {
 }
 
 impl VertexArray {
-    pub fn new() -> VertexArray {
-        let gl_vao = gl().create_vertex_array().unwrap();
+    pub fn new(gl: &WebGl2RenderingContext) -> VertexArray {
+        let gl_vao = gl.create_vertex_array().unwrap();
 
         Self { gl_vao }
     }
 
-    pub fn bind(&self) {
-        gl().bind_vertex_array(Some(&self.gl_vao));
+    pub fn bind(&self, gl: &WebGl2RenderingContext) {
+        gl.bind_vertex_array(Some(&self.gl_vao));
     }
 
+    #[allow(clippy::too_many_arguments)]
     pub fn attach_vertex_buffer(
         &self,
+        gl: &WebGl2RenderingContext,
         buffer: &VertexBuffer,
         location: i32,
         num_components: i32,
@@ -26,10 +27,10 @@ impl VertexArray {
         stride: i32,
         offset: i32,
     ) {
-        self.bind();
-        buffer.bind();
+        self.bind(gl);
+        buffer.bind(gl);
 
-        gl().vertex_attrib_pointer_with_i32(
+        gl.vertex_attrib_pointer_with_i32(
             location as u32,
             num_components,
             WebGl2RenderingContext::FLOAT,
@@ -37,7 +38,7 @@ impl VertexArray {
             stride,
             offset,
         );
-        gl().enable_vertex_attrib_array(location as u32);
+        gl.enable_vertex_attrib_array(location as u32);
     }
 }
 
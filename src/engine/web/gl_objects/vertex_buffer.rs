@@ -1,4 +1,3 @@
-use crate::gl;
 use web_sys::{WebGl2RenderingContext, WebGlBuffer};
 
 pub struct VertexBuffer {
@@ -6,9 +5,9 @@ pub struct VertexBuffer {
 }
 
 impl VertexBuffer {
-    pub fn new(vertices: &[f32]) -> VertexBuffer {
-        let buffer = gl().create_buffer().unwrap();
-        gl().bind_buffer(WebGl2RenderingContext::ARRAY_BUFFER, Some(&buffer));
+    pub fn new(gl: &WebGl2RenderingContext, vertices: &[f32]) -> VertexBuffer {
+        let buffer = gl.create_buffer().unwrap();
+        gl.bind_buffer(WebGl2RenderingContext::ARRAY_BUFFER, Some(&buffer));
 
         // Note that `Float32Array::view` is somewhat dangerous (hence the
         // `unsafe`!). This is creating a raw view into our module's
@@ -21,7 +20,7 @@ impl VertexBuffer {
         unsafe {
             let vertices_view = js_sys::Float32Array::view(vertices);
 
-            gl().buffer_data_with_array_buffer_view(
+            gl.buffer_data_with_array_buffer_view(
                 WebGl2RenderingContext::ARRAY_BUFFER,
                 &vertices_view,
                 WebGl2RenderingContext::STATIC_DRAW,
@@ -31,21 +30,22 @@ impl VertexBuffer {
         Self { buffer }
     }
 
-    pub fn update(&self, vertices: &[f32]) {
-        self.bind();
+    /// Replaces the contents, hinting that the buffer keeps changing.
+    pub fn update(&self, gl: &WebGl2RenderingContext, vertices: &[f32]) {
+        self.bind(gl);
 
         unsafe {
             let vertices_view = js_sys::Float32Array::view(vertices);
 
-            gl().buffer_data_with_array_buffer_view(
+            gl.buffer_data_with_array_buffer_view(
                 WebGl2RenderingContext::ARRAY_BUFFER,
                 &vertices_view,
-                WebGl2RenderingContext::STATIC_DRAW,
+                WebGl2RenderingContext::DYNAMIC_DRAW,
             );
         }
     }
 
-    pub fn bind(&self) {
-        gl().bind_buffer(WebGl2RenderingContext::ARRAY_BUFFER, Some(&self.buffer));
+    pub fn bind(&self, gl: &WebGl2RenderingContext) {
+        gl.bind_buffer(WebGl2RenderingContext::ARRAY_BUFFER, Some(&self.buffer));
     }
 }
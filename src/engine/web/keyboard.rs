@@ -0,0 +1,52 @@
+use crate::engine::input::source::EventQueue;
+use crate::engine::input::InputEvent;
+use wasm_bindgen::closure::Closure;
+use wasm_bindgen::JsCast;
+use web_sys::KeyboardEvent;
+
+/// Registers `keydown`/`keyup` listeners on the document that push onto
+/// `events`, along with repeats and typed characters.
+pub fn listen(events: EventQueue) {
+    let document = web_sys::window().unwrap().document().unwrap();
+
+    let keydown_events = events.clone();
+    let keydown_closure = Closure::new(Box::new(move |event: KeyboardEvent| {
+        if event.repeat() {
+            keydown_events.push(InputEvent::KeyRepeat { code: event.code() });
+        } else {
+            keydown_events.push(InputEvent::Key {
+                code: event.code(),
+                down: true,
+            });
+        }
+
+        // Named keys such as `Enter` have multi-character `key`s, and
+        // shortcuts aren't typing.
+        let key = event.key();
+        let mut characters = key.chars();
+        if let (Some(character), None) = (characters.next(), characters.next()) {
+            if !event.ctrl_key() && !event.meta_key() {
+                keydown_events.push(InputEvent::Text { character });
+            }
+        }
+    }) as Box<dyn FnMut(_)>);
+
+    document
+        .add_event_listener_with_callback("keydown", keydown_closure.as_ref().unchecked_ref())
+        .expect("Should be able to add listener.");
+
+    keydown_closure.forget();
+
+    let keyup_closure = Closure::new(Box::new(move |event: KeyboardEvent| {
+        events.push(InputEvent::Key {
+            code: event.code(),
+            down: false,
+        });
+    }) as Box<dyn FnMut(_)>);
+
+    document
+        .add_event_listener_with_callback("keyup", keyup_closure.as_ref().unchecked_ref())
+        .expect("Should be able to add listener.");
+
+    keyup_closure.forget();
+}
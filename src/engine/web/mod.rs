@@ -0,0 +1,59 @@
+//! Everything that talks to the browser. The rest of the engine only goes
+//! through the functions here, which fall back to plain Rust when built
+//! without the `web` feature or for a native target.
+
+#[cfg(feature = "web")]
+use wasm_bindgen::JsCast;
+#[cfg(feature = "web")]
+use web_sys::WebGl2RenderingContext;
+
+#[macro_export]
+macro_rules! log {
+    ( $( $t:tt )* ) => {
+        #[cfg(all(feature = "web", target_arch = "wasm32"))]
+        web_sys::console::log_1(&format!( $( $t )* ).into());
+        #[cfg(not(all(feature = "web", target_arch = "wasm32")))]
+        println!( $( $t )* );
+    }
+}
+
+#[cfg(feature = "web")]
+pub mod gamepad;
+#[cfg(feature = "web")]
+pub mod gl_objects;
+#[cfg(feature = "web")]
+pub mod keyboard;
+#[cfg(feature = "web")]
+pub mod pointer;
+#[cfg(feature = "web")]
+pub mod text_canvas;
+#[cfg(feature = "web")]
+pub mod touch;
+#[cfg(feature = "web")]
+pub mod webgl;
+
+#[cfg(all(feature = "web", target_arch = "wasm32"))]
+mod browser;
+#[cfg(all(feature = "web", target_arch = "wasm32"))]
+pub use browser::*;
+
+#[cfg(not(all(feature = "web", target_arch = "wasm32")))]
+mod native;
+#[cfg(not(all(feature = "web", target_arch = "wasm32")))]
+pub use native::*;
+
+/// Looks up the canvas with the given id and gets a WebGL2 context for it.
+#[cfg(feature = "web")]
+pub fn webgl_context(canvas_id: &str) -> WebGl2RenderingContext {
+    let document = web_sys::window().unwrap().document().unwrap();
+    let canvas = document.get_element_by_id(canvas_id).unwrap();
+    let canvas: web_sys::HtmlCanvasElement =
+        canvas.dyn_into::<web_sys::HtmlCanvasElement>().unwrap();
+
+    canvas
+        .get_context("webgl2")
+        .unwrap()
+        .unwrap()
+        .dyn_into::<WebGl2RenderingContext>()
+        .unwrap()
+}
@@ -0,0 +1,38 @@
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+// Outside the browser the files the dev server would serve are read straight
+// from `public/`.
+pub async fn get_string(url: &str) -> Result<String, String> {
+    std::fs::read_to_string(public_path(url)).map_err(|e| e.to_string())
+}
+
+pub async fn get_bytes(url: &str) -> Result<Vec<u8>, String> {
+    std::fs::read(public_path(url)).map_err(|e| e.to_string())
+}
+
+fn public_path(url: &str) -> std::path::PathBuf {
+    std::path::Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("public")
+        .join(url.trim_start_matches('/'))
+}
+
+/// Waits out a 60Hz frame and returns the current time in milliseconds.
+pub async fn next_animation_frame() -> f64 {
+    async_std::task::sleep(Duration::from_micros(16_667)).await;
+
+    now()
+}
+
+/// There is no arcade menu to go back to outside the browser.
+pub fn exit() {
+    log!("Exit requested");
+}
+
+/// The system clock, in milliseconds.
+pub fn now() -> f64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_secs_f64()
+        * 1000.0
+}
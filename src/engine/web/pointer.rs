@@ -0,0 +1,95 @@
+use crate::engine::input::source::EventQueue;
+use crate::engine::input::InputEvent;
+use wasm_bindgen::closure::Closure;
+use wasm_bindgen::JsCast;
+use web_sys::{HtmlCanvasElement, MouseEvent, PointerEvent, WheelEvent};
+
+/// Registers pointer and wheel listeners on the canvas with the given id.
+/// Button releases are caught on the document so a drag that ends off the
+/// canvas still lets go.
+pub fn listen(events: EventQueue, canvas_id: &str) {
+    let document = web_sys::window().unwrap().document().unwrap();
+    let canvas = document
+        .get_element_by_id(canvas_id)
+        .unwrap()
+        .dyn_into::<HtmlCanvasElement>()
+        .unwrap();
+
+    let move_canvas = canvas.clone();
+    let move_events = events.clone();
+    let pointermove_closure = Closure::new(Box::new(move |event: PointerEvent| {
+        move_events.push(pointer_move(&move_canvas, &event));
+    }) as Box<dyn FnMut(_)>);
+
+    canvas
+        .add_event_listener_with_callback(
+            "pointermove",
+            pointermove_closure.as_ref().unchecked_ref(),
+        )
+        .expect("Should be able to add listener.");
+
+    pointermove_closure.forget();
+
+    let down_canvas = canvas.clone();
+    let down_events = events.clone();
+    let pointerdown_closure = Closure::new(Box::new(move |event: PointerEvent| {
+        down_events.push(pointer_move(&down_canvas, &event));
+        down_events.push(InputEvent::PointerButton {
+            button: event.button() as u16,
+            down: true,
+        });
+    }) as Box<dyn FnMut(_)>);
+
+    canvas
+        .add_event_listener_with_callback(
+            "pointerdown",
+            pointerdown_closure.as_ref().unchecked_ref(),
+        )
+        .expect("Should be able to add listener.");
+
+    pointerdown_closure.forget();
+
+    let up_events = events.clone();
+    let pointerup_closure = Closure::new(Box::new(move |event: PointerEvent| {
+        up_events.push(InputEvent::PointerButton {
+            button: event.button() as u16,
+            down: false,
+        });
+    }) as Box<dyn FnMut(_)>);
+
+    document
+        .add_event_listener_with_callback("pointerup", pointerup_closure.as_ref().unchecked_ref())
+        .expect("Should be able to add listener.");
+
+    pointerup_closure.forget();
+
+    let wheel_closure = Closure::new(Box::new(move |event: WheelEvent| {
+        event.prevent_default();
+        events.push(InputEvent::Wheel {
+            delta: event.delta_y(),
+        });
+    }) as Box<dyn FnMut(_)>);
+
+    canvas
+        .add_event_listener_with_callback("wheel", wheel_closure.as_ref().unchecked_ref())
+        .expect("Should be able to add listener.");
+
+    wheel_closure.forget();
+}
+
+fn pointer_move(canvas: &HtmlCanvasElement, event: &MouseEvent) -> InputEvent {
+    let (x, y) = client_to_screen(canvas, event.client_x(), event.client_y());
+
+    InputEvent::PointerMove { x, y }
+}
+
+/// Scales a position in the page to 1920x1080 screen coordinates on the
+/// canvas.
+pub fn client_to_screen(canvas: &HtmlCanvasElement, client_x: i32, client_y: i32) -> (f64, f64) {
+    let rect = canvas.get_bounding_client_rect();
+
+    (
+        (client_x as f64 - rect.left()) / rect.width() * 1920.0,
+        (client_y as f64 - rect.top()) / rect.height() * 1080.0,
+    )
+}
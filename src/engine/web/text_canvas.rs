@@ -0,0 +1,39 @@
+use crate::engine::vec2f::Vec2f;
+use wasm_bindgen::prelude::wasm_bindgen;
+
+#[wasm_bindgen(module = "/web.js")]
+extern "C" {
+    fn makeTextCanvas(text: String, width: f64, height: f64) -> web_sys::CanvasRenderingContext2d;
+    fn changeTextFillStyle(style: String);
+}
+
+/// The 2D canvas overlaid on the game for drawing text.
+pub struct TextCanvas {
+    context: web_sys::CanvasRenderingContext2d,
+    width: f64,
+    height: f64,
+}
+
+impl TextCanvas {
+    pub fn new(width: f64, height: f64) -> Self {
+        Self {
+            context: makeTextCanvas("".into(), width, height),
+            width,
+            height,
+        }
+    }
+
+    pub fn clear(&self) {
+        self.context.clear_rect(0.0, 0.0, self.width, self.height);
+    }
+
+    pub fn fill_text(&self, text: &str, pos: Vec2f) {
+        self.context
+            .fill_text(text, pos.x, pos.y)
+            .expect("Should be able to draw text.");
+    }
+
+    pub fn set_fill_style(&self, style: String) {
+        changeTextFillStyle(style);
+    }
+}
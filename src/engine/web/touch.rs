@@ -0,0 +1,53 @@
+use crate::engine::input::source::EventQueue;
+use crate::engine::input::touch::TouchPhase;
+use crate::engine::input::InputEvent;
+use crate::engine::web::pointer::client_to_screen;
+use wasm_bindgen::closure::Closure;
+use wasm_bindgen::JsCast;
+use web_sys::{HtmlCanvasElement, TouchEvent};
+
+/// Registers touch listeners on the canvas with the given id. Default
+/// handling is suppressed so dragging a finger doesn't scroll the page.
+pub fn listen(events: EventQueue, canvas_id: &str) {
+    let document = web_sys::window().unwrap().document().unwrap();
+    let canvas = document
+        .get_element_by_id(canvas_id)
+        .unwrap()
+        .dyn_into::<HtmlCanvasElement>()
+        .unwrap();
+
+    for (name, phase) in [
+        ("touchstart", TouchPhase::Start),
+        ("touchmove", TouchPhase::Move),
+        ("touchend", TouchPhase::End),
+        ("touchcancel", TouchPhase::Cancel),
+    ] {
+        let touch_canvas = canvas.clone();
+        let touch_events = events.clone();
+        let closure = Closure::new(Box::new(move |event: TouchEvent| {
+            event.prevent_default();
+
+            let touches = event.changed_touches();
+            for i in 0..touches.length() {
+                let touch = match touches.item(i) {
+                    None => continue,
+                    Some(touch) => touch,
+                };
+
+                let (x, y) = client_to_screen(&touch_canvas, touch.client_x(), touch.client_y());
+                touch_events.push(InputEvent::Touch {
+                    id: touch.identifier(),
+                    phase,
+                    x,
+                    y,
+                });
+            }
+        }) as Box<dyn FnMut(_)>);
+
+        canvas
+            .add_event_listener_with_callback(name, closure.as_ref().unchecked_ref())
+            .expect("Should be able to add listener.");
+
+        closure.forget();
+    }
+}
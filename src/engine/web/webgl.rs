@@ -0,0 +1,281 @@
+use crate::engine::camera::Camera;
+use crate::engine::renderer::{Renderer, Uniform, VertexLayout};
+use crate::engine::transform::Transform;
+use crate::engine::web::gl_objects::vertex_array::VertexArray;
+use crate::engine::web::gl_objects::vertex_buffer::VertexBuffer;
+use image::RgbaImage;
+use web_sys::{WebGl2RenderingContext, WebGlProgram, WebGlShader, WebGlTexture};
+
+struct GlMesh {
+    vert_count: i32,
+    layout: VertexLayout,
+    va: VertexArray,
+    vb: VertexBuffer,
+}
+
+pub struct WebGlRenderer {
+    gl: WebGl2RenderingContext,
+    programs: Vec<WebGlProgram>,
+    textures: Vec<WebGlTexture>,
+    meshes: Vec<GlMesh>,
+}
+
+impl WebGlRenderer {
+    pub fn new(gl: WebGl2RenderingContext) -> Self {
+        gl.enable(WebGl2RenderingContext::BLEND);
+
+        gl.blend_func(
+            WebGl2RenderingContext::SRC_ALPHA,
+            WebGl2RenderingContext::ONE_MINUS_SRC_ALPHA,
+        );
+
+        gl.pixel_storei(WebGl2RenderingContext::UNPACK_PREMULTIPLY_ALPHA_WEBGL, 0);
+
+        Self {
+            gl,
+            programs: Vec::new(),
+            textures: Vec::new(),
+            meshes: Vec::new(),
+        }
+    }
+
+    fn set_uniform(&self, shader: i32, name: &str, value: Uniform) {
+        let location = self
+            .gl
+            .get_uniform_location(&self.programs[shader as usize], name);
+
+        match value {
+            Uniform::Float(data) => self.gl.uniform1f(location.as_ref(), data),
+            Uniform::Int(data) => self.gl.uniform1i(location.as_ref(), data),
+            Uniform::Vec2(data) => self.gl.uniform2fv_with_f32_array(location.as_ref(), &data),
+            Uniform::Vec4(data) => self.gl.uniform4fv_with_f32_array(location.as_ref(), &data),
+        }
+    }
+
+    fn compile_shader(&self, shader_type: u32, source: &str) -> Result<WebGlShader, String> {
+        let gl = &self.gl;
+        let shader = gl
+            .create_shader(shader_type)
+            .ok_or_else(|| String::from("Unable to create shader object"))?;
+        gl.shader_source(&shader, source);
+        gl.compile_shader(&shader);
+
+        if gl
+            .get_shader_parameter(&shader, WebGl2RenderingContext::COMPILE_STATUS)
+            .as_bool()
+            .unwrap_or(false)
+        {
+            Ok(shader)
+        } else {
+            let error: String = gl
+                .get_shader_info_log(&shader)
+                .unwrap_or_else(|| String::from("Unknown error creating shader"));
+
+            log!("Shader compilation error: {error}");
+
+            Err(String::from("Shader compilation error:\n") + &*error)
+        }
+    }
+
+    fn link_program(
+        &self,
+        vert_shader: &WebGlShader,
+        frag_shader: &WebGlShader,
+    ) -> Result<WebGlProgram, String> {
+        let gl = &self.gl;
+        let program = gl
+            .create_program()
+            .ok_or_else(|| String::from("Unable to create shader object"))?;
+
+        gl.attach_shader(&program, vert_shader);
+        gl.attach_shader(&program, frag_shader);
+        gl.link_program(&program);
+
+        if gl
+            .get_program_parameter(&program, WebGl2RenderingContext::LINK_STATUS)
+            .as_bool()
+            .unwrap_or(false)
+        {
+            Ok(program)
+        } else {
+            Err(String::from("Shader linking error:\n")
+                + &*gl
+                    .get_program_info_log(&program)
+                    .unwrap_or_else(|| String::from("Unknown error creating program object")))
+        }
+    }
+}
+
+impl Renderer for WebGlRenderer {
+    fn create_shader(&mut self, vert_src: &str, frag_src: &str) -> Result<i32, String> {
+        let vert_shader = self.compile_shader(WebGl2RenderingContext::VERTEX_SHADER, vert_src)?;
+        let frag_shader = self.compile_shader(WebGl2RenderingContext::FRAGMENT_SHADER, frag_src)?;
+
+        let program = self.link_program(&vert_shader, &frag_shader)?;
+        self.programs.push(program);
+
+        Ok(self.programs.len() as i32 - 1)
+    }
+
+    fn create_texture(&mut self, image: &RgbaImage) -> i32 {
+        let gl = &self.gl;
+        let web_gl_texture = gl
+            .create_texture()
+            .expect("Should be able to create texture");
+        gl.bind_texture(WebGl2RenderingContext::TEXTURE_2D, Some(&web_gl_texture));
+
+        gl.tex_parameteri(
+            WebGl2RenderingContext::TEXTURE_2D,
+            WebGl2RenderingContext::TEXTURE_WRAP_S,
+            WebGl2RenderingContext::REPEAT as i32,
+        );
+        gl.tex_parameteri(
+            WebGl2RenderingContext::TEXTURE_2D,
+            WebGl2RenderingContext::TEXTURE_WRAP_T,
+            WebGl2RenderingContext::REPEAT as i32,
+        );
+        gl.tex_parameteri(
+            WebGl2RenderingContext::TEXTURE_2D,
+            WebGl2RenderingContext::TEXTURE_MIN_FILTER,
+            WebGl2RenderingContext::NEAREST as i32,
+        );
+        gl.tex_parameteri(
+            WebGl2RenderingContext::TEXTURE_2D,
+            WebGl2RenderingContext::TEXTURE_MAG_FILTER,
+            WebGl2RenderingContext::NEAREST as i32,
+        );
+
+        gl.tex_image_2d_with_i32_and_i32_and_i32_and_format_and_type_and_opt_u8_array(
+            WebGl2RenderingContext::TEXTURE_2D,
+            0,
+            WebGl2RenderingContext::RGBA as i32,
+            image.width() as i32,
+            image.height() as i32,
+            0,
+            WebGl2RenderingContext::RGBA,
+            WebGl2RenderingContext::UNSIGNED_BYTE,
+            Some(image.as_raw()),
+        )
+        .expect("Should be able to upload image");
+
+        self.textures.push(web_gl_texture);
+        self.textures.len() as i32 - 1
+    }
+
+    fn create_mesh(&mut self, vertices: &[f32], layout: VertexLayout) -> i32 {
+        let va = VertexArray::new(&self.gl);
+        let vb = VertexBuffer::new(&self.gl, vertices);
+
+        match layout {
+            VertexLayout::Position => {
+                va.attach_vertex_buffer(&self.gl, &vb, 0, 2, false, 0, 0);
+            }
+            VertexLayout::PositionTexCoord => {
+                va.attach_vertex_buffer(&self.gl, &vb, 0, 2, false, 16, 0);
+                va.attach_vertex_buffer(&self.gl, &vb, 1, 2, false, 16, 8);
+            }
+            VertexLayout::Sprite => {
+                va.attach_vertex_buffer(&self.gl, &vb, 0, 2, false, 32, 0);
+                va.attach_vertex_buffer(&self.gl, &vb, 1, 2, false, 32, 8);
+                va.attach_vertex_buffer(&self.gl, &vb, 2, 4, false, 32, 16);
+            }
+        }
+
+        self.meshes.push(GlMesh {
+            vert_count: (vertices.len() / layout.floats_per_vertex()) as i32,
+            layout,
+            va,
+            vb,
+        });
+        self.meshes.len() as i32 - 1
+    }
+
+    fn update_mesh(&mut self, mesh: i32, vertices: &[f32]) {
+        let mesh = &mut self.meshes[mesh as usize];
+        mesh.vb.update(&self.gl, vertices);
+        mesh.vert_count = (vertices.len() / mesh.layout.floats_per_vertex()) as i32;
+    }
+
+    fn bind_shader(&mut self, shader: i32) {
+        self.gl.use_program(Some(&self.programs[shader as usize]));
+    }
+
+    fn bind_texture(&mut self, texture: i32) {
+        self.gl.active_texture(WebGl2RenderingContext::TEXTURE0);
+        self.gl.bind_texture(
+            WebGl2RenderingContext::TEXTURE_2D,
+            Some(&self.textures[texture as usize]),
+        );
+    }
+
+    fn uniform(&mut self, shader: i32, name: &str, value: Uniform) {
+        self.set_uniform(shader, name, value);
+    }
+
+    fn uniform_transform(&mut self, shader: i32, transform: &Transform) {
+        self.set_uniform(
+            shader,
+            "transform.position",
+            Uniform::Vec2(screen_coordinates(&transform.position.as_arr())),
+        );
+        self.set_uniform(
+            shader,
+            "transform.size",
+            Uniform::Vec2([transform.size.x as f32, transform.size.y as f32]),
+        );
+        self.set_uniform(
+            shader,
+            "transform.rotation",
+            Uniform::Float(transform.rotation as f32),
+        );
+    }
+
+    fn uniform_camera(&mut self, shader: i32, camera: &Camera) {
+        self.set_uniform(
+            shader,
+            "camera.position",
+            Uniform::Vec2(screen_coordinates(&camera.position.as_arr())),
+        );
+        self.set_uniform(shader, "camera.zoom", Uniform::Float(camera.zoom as f32));
+    }
+
+    fn draw_mesh(&mut self, mesh: i32) {
+        let mesh = &self.meshes[mesh as usize];
+        mesh.va.bind(&self.gl);
+        self.gl
+            .draw_arrays(WebGl2RenderingContext::TRIANGLES, 0, mesh.vert_count);
+    }
+
+    fn clear(&mut self, color: [f32; 4]) {
+        self.gl.clear_color(color[0], color[1], color[2], color[3]);
+        self.gl.clear(WebGl2RenderingContext::COLOR_BUFFER_BIT);
+    }
+
+    fn frame(&self) -> Option<RgbaImage> {
+        let width = self.gl.drawing_buffer_width() as u32;
+        let height = self.gl.drawing_buffer_height() as u32;
+        let mut pixels = vec![0u8; (width * height * 4) as usize];
+
+        self.gl
+            .read_pixels_with_opt_u8_array(
+                0,
+                0,
+                width as i32,
+                height as i32,
+                WebGl2RenderingContext::RGBA,
+                WebGl2RenderingContext::UNSIGNED_BYTE,
+                Some(&mut pixels),
+            )
+            .ok()?;
+
+        // GL reads bottom row first.
+        let mut frame = RgbaImage::from_raw(width, height, pixels)?;
+        image::imageops::flip_vertical_in_place(&mut frame);
+
+        Some(frame)
+    }
+}
+
+fn screen_coordinates(data: &[f64; 2]) -> [f32; 2] {
+    [data[0] as f32 / 960.0, data[1] as f32 / 540.0]
+}
@@ -0,0 +1,143 @@
+use arcamedia_engine::engine::input::bindings::{Bindings, KeyAxis};
+use arcamedia_engine::engine::input::{Input, InputEvent};
+
+const TEXT: &str = "# Comments and blank lines are skipped.
+
+action jump Space GamepadA
+action fire KeyF
+axis move_x KeyA KeyD
+axis move_x ArrowLeft ArrowRight
+analog move_x JoystickX GamepadLeftX
+";
+
+fn key(input: &mut Input, code: &str, down: bool) {
+    input.push_event(InputEvent::Key {
+        code: code.into(),
+        down,
+    });
+    input.poll_events(1.0 / 60.0);
+}
+
+// Sets the up button on the d-pad of pad 0.
+fn pad_up(input: &mut Input, value: f64) {
+    input.push_event(InputEvent::GamepadButton {
+        pad: 0,
+        button: 12,
+        value,
+    });
+    input.poll_events(1.0 / 60.0);
+}
+
+#[test]
+fn parse_reads_actions_axes_and_analog_sources() {
+    let bindings = Bindings::parse(TEXT).unwrap();
+
+    assert_eq!(bindings.action_keys("jump"), &["Space", "GamepadA"]);
+    assert_eq!(
+        bindings.axis_keys("move_x"),
+        &[
+            KeyAxis {
+                negative: "KeyA".into(),
+                positive: "KeyD".into(),
+            },
+            KeyAxis {
+                negative: "ArrowLeft".into(),
+                positive: "ArrowRight".into(),
+            },
+        ]
+    );
+    assert_eq!(
+        bindings.analog_sources("move_x"),
+        &["JoystickX", "GamepadLeftX"]
+    );
+    assert!(bindings.action_keys("missing").is_empty());
+}
+
+#[test]
+fn serialize_round_trips_sorted_by_name() {
+    let bindings = Bindings::parse(TEXT).unwrap();
+    let text = bindings.serialize();
+
+    assert_eq!(
+        text,
+        "action fire KeyF
+action jump Space GamepadA
+axis move_x KeyA KeyD
+axis move_x ArrowLeft ArrowRight
+analog move_x JoystickX GamepadLeftX
+"
+    );
+    assert_eq!(Bindings::parse(&text), Ok(bindings));
+    assert_eq!(
+        Bindings::parse(&Bindings::new().serialize()),
+        Ok(Bindings::new())
+    );
+}
+
+#[test]
+fn parse_rejects_bad_lines() {
+    for line in [
+        "action jump",
+        "axis move_x KeyA",
+        "axis move_x KeyA KeyD KeyW",
+        "analog move_x",
+        "button jump Space",
+    ] {
+        assert!(
+            Bindings::parse(&format!("action fire KeyF\n{line}\n")).is_err(),
+            "{line}"
+        );
+    }
+}
+
+#[test]
+fn rebinding_replaces_the_keys() {
+    let mut bindings = Bindings::parse(TEXT).unwrap();
+
+    bindings.rebind_action("jump", "KeyW");
+    bindings.bind_action("jump", "KeyW");
+    bindings.rebind_axis("move_x", "KeyJ", "KeyL");
+    bindings.unbind_analog("move_x");
+    bindings.unbind_action("fire");
+
+    assert_eq!(
+        bindings.serialize(),
+        "action jump KeyW\naxis move_x KeyJ KeyL\n"
+    );
+}
+
+#[test]
+fn action_edges_follow_all_its_keys_together() {
+    let mut input = Input::new();
+    input.push_event(InputEvent::GamepadConnected { pad: 0 });
+
+    key(&mut input, "ArrowUp", true);
+    assert!(input.action_pressed("up"));
+    assert!(input.action_down("up"));
+    input.flush_pressed_map();
+
+    // A second key for the same action neither presses it again nor, when
+    // the first is let go, releases it.
+    pad_up(&mut input, 1.0);
+    assert!(!input.action_pressed("up"));
+    input.flush_pressed_map();
+
+    key(&mut input, "ArrowUp", false);
+    assert!(!input.action_released("up"));
+    assert!(input.action_down("up"));
+    input.flush_pressed_map();
+
+    pad_up(&mut input, 0.0);
+    assert!(input.action_released("up"));
+    assert!(!input.action_down("up"));
+    input.flush_pressed_map();
+
+    // Tapped within one frame, it is both pressed and released.
+    input.push_event(InputEvent::Key {
+        code: "ArrowUp".into(),
+        down: true,
+    });
+    key(&mut input, "ArrowUp", false);
+    assert!(input.action_pressed("up"));
+    assert!(input.action_released("up"));
+}
@@ -0,0 +1,167 @@
+use arcamedia_engine::engine::collision::Collision;
+use arcamedia_engine::engine::context::EngineContext;
+use arcamedia_engine::engine::object::Object;
+use arcamedia_engine::engine::object_manager::{ObjectId, ObjectManager};
+use arcamedia_engine::engine::renderer::headless::HeadlessRenderer;
+use arcamedia_engine::engine::tag::Tag;
+use arcamedia_engine::engine::transform::Transform;
+use arcamedia_engine::engine::vec2f::Vec2f;
+use arcamedia_engine::engine::Engine;
+use arcamedia_engine::object;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+#[derive(Clone, PartialEq, Debug)]
+enum Event {
+    Enter(&'static str, ObjectId, Vec2f),
+    Stay(&'static str, ObjectId, Vec2f),
+    Exit(&'static str, ObjectId),
+}
+
+type Log = Rc<RefCell<Vec<Event>>>;
+
+struct Probe {
+    name: &'static str,
+    transform: Transform,
+    log: Log,
+    tags: Vec<Tag>,
+}
+
+impl Object for Probe {
+    fn transform(&self) -> Option<&Transform> {
+        Some(&self.transform)
+    }
+
+    fn transform_mut(&mut self) -> Option<&mut Transform> {
+        Some(&mut self.transform)
+    }
+
+    fn collides(&self) -> bool {
+        true
+    }
+
+    fn on_collision_enter(&mut self, _ctx: &mut EngineContext, collision: &Collision) {
+        self.log.borrow_mut().push(Event::Enter(
+            self.name,
+            collision.other,
+            collision.manifold.normal.clone(),
+        ));
+    }
+
+    fn on_collision_stay(&mut self, _ctx: &mut EngineContext, collision: &Collision) {
+        self.log.borrow_mut().push(Event::Stay(
+            self.name,
+            collision.other,
+            collision.manifold.normal.clone(),
+        ));
+    }
+
+    fn on_collision_exit(&mut self, _ctx: &mut EngineContext, other: ObjectId) {
+        self.log.borrow_mut().push(Event::Exit(self.name, other));
+    }
+
+    fn tags(&self) -> &Vec<Tag> {
+        &self.tags
+    }
+
+    fn tags_mut(&mut self) -> &mut Vec<Tag> {
+        &mut self.tags
+    }
+}
+
+fn probe(ctx: &mut EngineContext, log: &Log, name: &'static str, x: f64) -> ObjectId {
+    object!(
+        ctx,
+        Probe {
+            name,
+            transform: Transform::new([x, 0.0].into(), [10.0, 10.0].into(), 0.0),
+            log: log.clone(),
+            tags: Vec::new(),
+        }
+    )
+}
+
+fn step(ctx: &mut EngineContext, log: &Log) -> Vec<Event> {
+    ObjectManager::apply_pending(ctx);
+    ObjectManager::tick(ctx, 0.0);
+    log.borrow_mut().drain(..).collect()
+}
+
+fn move_to(ctx: &EngineContext, id: ObjectId, x: f64) {
+    let object = ctx.om.get(id).unwrap();
+    object.borrow_mut().transform_mut().unwrap().position.x = x;
+}
+
+#[test]
+fn enter_then_stay_then_exit() {
+    let mut ctx = Engine::init_headless(HeadlessRenderer::new());
+    let log = Log::default();
+
+    let a = probe(&mut ctx, &log, "a", 0.0);
+    let b = probe(&mut ctx, &log, "b", 8.0);
+    let right = Vec2f::new(1.0, 0.0);
+    let left = Vec2f::new(-1.0, 0.0);
+
+    // Each side sees the other, with the normal pointing away from itself.
+    assert_eq!(
+        step(&mut ctx, &log),
+        vec![
+            Event::Enter("a", b, right.clone()),
+            Event::Enter("b", a, left.clone()),
+        ]
+    );
+    assert_eq!(
+        step(&mut ctx, &log),
+        vec![Event::Stay("a", b, right), Event::Stay("b", a, left)]
+    );
+
+    move_to(&ctx, b, 50.0);
+    assert_eq!(
+        step(&mut ctx, &log),
+        vec![Event::Exit("a", b), Event::Exit("b", a)]
+    );
+    assert_eq!(step(&mut ctx, &log), vec![]);
+}
+
+#[test]
+fn exits_come_before_enters_and_stays_in_id_order() {
+    let mut ctx = Engine::init_headless(HeadlessRenderer::new());
+    let log = Log::default();
+
+    let a = probe(&mut ctx, &log, "a", 0.0);
+    let b = probe(&mut ctx, &log, "b", 8.0);
+    let c = probe(&mut ctx, &log, "c", -8.0);
+    step(&mut ctx, &log);
+
+    // `c` leaves `a` as `b` is still touching it and a new `d` arrives.
+    move_to(&ctx, c, -50.0);
+    let d = probe(&mut ctx, &log, "d", 15.0);
+    let events = step(&mut ctx, &log);
+
+    let (right, left) = (Vec2f::new(1.0, 0.0), Vec2f::new(-1.0, 0.0));
+    assert_eq!(
+        events,
+        vec![
+            Event::Exit("a", c),
+            Event::Exit("c", a),
+            Event::Stay("a", b, right.clone()),
+            Event::Stay("b", a, left.clone()),
+            Event::Enter("b", d, right),
+            Event::Enter("d", b, left),
+        ]
+    );
+}
+
+#[test]
+fn despawning_ends_the_contact() {
+    let mut ctx = Engine::init_headless(HeadlessRenderer::new());
+    let log = Log::default();
+
+    probe(&mut ctx, &log, "a", 0.0);
+    let b = probe(&mut ctx, &log, "b", 8.0);
+    step(&mut ctx, &log);
+
+    ctx.om.despawn(b);
+    assert_eq!(step(&mut ctx, &log), vec![Event::Exit("a", b)]);
+    assert_eq!(step(&mut ctx, &log), vec![]);
+}
@@ -0,0 +1,110 @@
+use arcamedia_engine::engine::context::EngineContext;
+use arcamedia_engine::engine::game_loop::GameLoop;
+use arcamedia_engine::engine::object::rect::Rect;
+use arcamedia_engine::engine::object_manager::ObjectManager;
+use arcamedia_engine::engine::renderer::headless::{HeadlessRenderer, RenderCommand};
+use arcamedia_engine::engine::Engine;
+use arcamedia_engine::object;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+fn context() -> EngineContext {
+    Engine::init_headless(HeadlessRenderer::new())
+}
+
+#[test]
+fn accumulator_spends_whole_steps_and_keeps_the_rest() {
+    let mut ctx = context();
+    let mut game_loop = GameLoop::new(4.0);
+
+    assert_eq!(game_loop.advance(&mut ctx, 0.625), 0.5);
+    assert_eq!(game_loop.steps(), 2);
+
+    assert_eq!(game_loop.advance(&mut ctx, 0.125), 0.0);
+    assert_eq!(game_loop.steps(), 3);
+
+    assert_eq!(game_loop.advance(&mut ctx, -1.0), 0.0);
+    assert_eq!(game_loop.steps(), 3);
+}
+
+#[test]
+fn frame_timestamps_are_milliseconds() {
+    let mut ctx = context();
+    let mut game_loop = GameLoop::new(4.0);
+
+    game_loop.frame(&mut ctx, 1000.0);
+    assert_eq!(game_loop.steps(), 0);
+
+    game_loop.frame(&mut ctx, 1500.0);
+    assert_eq!(game_loop.steps(), 2);
+}
+
+#[test]
+fn long_hitch_is_capped_at_max_steps() {
+    let mut ctx = context();
+    let mut game_loop = GameLoop::new(4.0);
+    game_loop.max_steps = 3;
+
+    let alpha = game_loop.advance(&mut ctx, 10.125);
+    assert_eq!(game_loop.steps(), 3);
+    assert_eq!(alpha, 0.5);
+
+    game_loop.advance(&mut ctx, 0.0);
+    assert_eq!(game_loop.steps(), 3);
+}
+
+#[test]
+fn paused_loop_only_steps_on_demand() {
+    let mut ctx = context();
+    let mut game_loop = GameLoop::new(4.0);
+
+    game_loop.advance(&mut ctx, 0.375);
+    game_loop.pause();
+    assert!(game_loop.is_paused());
+
+    assert_eq!(game_loop.advance(&mut ctx, 5.0), 0.5);
+    assert_eq!(game_loop.steps(), 1);
+
+    game_loop.step_once(&mut ctx);
+    assert_eq!(game_loop.steps(), 2);
+
+    game_loop.resume();
+    assert_eq!(game_loop.advance(&mut ctx, 0.125), 0.0);
+    assert_eq!(game_loop.steps(), 3);
+}
+
+#[test]
+fn render_blends_between_the_last_two_steps() {
+    let renderer = HeadlessRenderer::new();
+    let commands = renderer.commands();
+    let mut ctx = Engine::init_headless(renderer);
+    let mut game_loop = GameLoop::new(4.0);
+
+    let id = object!(
+        ctx,
+        Rect::new(
+            [0.0, 0.0].into(),
+            [10.0, 10.0].into(),
+            0.0,
+            [1.0, 1.0, 1.0, 1.0],
+            false,
+        )
+    );
+    ObjectManager::apply_pending(&mut ctx);
+    game_loop.step_once(&mut ctx);
+
+    let object = ctx.om.get(id).unwrap();
+    object.borrow_mut().transform_mut().unwrap().position = [10.0, 0.0].into();
+
+    game_loop.render(&mut ctx, 0.5);
+
+    let x = commands
+        .borrow()
+        .iter()
+        .find_map(|command| match command {
+            RenderCommand::Sprite(sprite) => Some(sprite.transform.position.x),
+            _ => None,
+        })
+        .unwrap();
+    assert_eq!(x, 5.0);
+}
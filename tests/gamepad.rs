@@ -0,0 +1,188 @@
+use arcamedia_engine::engine::input::gamepad::{
+    button_code, GamepadState, Gamepads, LEFT_TRIGGER, RIGHT_TRIGGER,
+};
+use arcamedia_engine::engine::input::{Input, InputEvent};
+
+fn close(a: f64, b: f64) -> bool {
+    (a - b).abs() < 1e-9
+}
+
+// A connected pad 0 reading `buttons` and `axes`.
+fn pad(buttons: Vec<f64>, axes: Vec<f64>) -> Gamepads {
+    let mut gamepads = Gamepads::new();
+    gamepads.pads = vec![Some(GamepadState { buttons, axes })];
+    gamepads
+}
+
+#[test]
+fn diff_reports_connections_and_changed_readings() {
+    let mut gamepads = Gamepads::new();
+    let snapshot = vec![
+        None,
+        Some(GamepadState {
+            buttons: vec![0.0, 1.0],
+            axes: vec![0.25, 0.0],
+        }),
+    ];
+
+    let events = gamepads.diff(&snapshot);
+    assert_eq!(
+        events,
+        vec![
+            InputEvent::GamepadConnected { pad: 1 },
+            InputEvent::GamepadButton {
+                pad: 1,
+                button: 1,
+                value: 1.0,
+            },
+            InputEvent::GamepadAxis {
+                pad: 1,
+                axis: 0,
+                value: 0.25,
+            },
+        ]
+    );
+    for event in &events {
+        gamepads.apply(event);
+    }
+    let state = gamepads.get(1).unwrap();
+    assert_eq!(state.buttons, vec![0.0, 1.0]);
+    assert_eq!((state.axis(0), state.axis(1)), (0.25, 0.0));
+    assert_eq!(gamepads.connected, vec![1]);
+
+    // Noise below the rounding is no change.
+    let snapshot = vec![
+        None,
+        Some(GamepadState {
+            buttons: vec![0.0, 0.9999],
+            axes: vec![0.2501, -0.5],
+        }),
+    ];
+    assert_eq!(
+        gamepads.diff(&snapshot),
+        vec![InputEvent::GamepadAxis {
+            pad: 1,
+            axis: 1,
+            value: -0.5,
+        }]
+    );
+
+    assert_eq!(
+        gamepads.diff(&[]),
+        vec![InputEvent::GamepadDisconnected { pad: 1 }]
+    );
+}
+
+#[test]
+fn stick_cuts_the_deadzone_and_rescales() {
+    // The deadzone is 0.2 of full deflection.
+    let gamepads = pad(Vec::new(), vec![0.15, -0.1, 0.6, 0.0]);
+    let left = gamepads.stick(0, 0);
+    assert_eq!((left.x, left.y), (0.0, 0.0));
+
+    let right = gamepads.stick(0, 1);
+    assert!(close(right.x, 0.5) && close(right.y, 0.0));
+
+    // Up on the pad is negative, in the world positive.
+    let gamepads = pad(Vec::new(), vec![0.0, -1.0]);
+    let left = gamepads.stick(0, 0);
+    assert!(close(left.x, 0.0) && close(left.y, 1.0));
+
+    // Past full in a corner, clamped to 1.
+    let gamepads = pad(Vec::new(), vec![1.0, 1.0]);
+    assert!(close(gamepads.stick(0, 0).len(), 1.0));
+
+    assert_eq!(gamepads.stick(3, 0).len(), 0.0);
+}
+
+#[test]
+fn trigger_cuts_the_deadzone_and_rescales() {
+    // The deadzone is 0.1 of full travel.
+    let mut buttons = vec![0.0; 8];
+    buttons[LEFT_TRIGGER] = 0.05;
+    buttons[RIGHT_TRIGGER] = 0.46;
+    let gamepads = pad(buttons, Vec::new());
+
+    assert_eq!(gamepads.trigger(0, LEFT_TRIGGER), 0.0);
+    assert!(close(gamepads.trigger(0, RIGHT_TRIGGER), 0.4));
+    assert_eq!(gamepads.trigger(1, RIGHT_TRIGGER), 0.0);
+    assert!(!gamepads.pressed(0, RIGHT_TRIGGER));
+}
+
+#[test]
+fn button_codes_follow_the_standard_layout() {
+    assert_eq!(button_code(0), "GamepadA");
+    assert_eq!(button_code(12), "GamepadUp");
+    assert_eq!(button_code(16), "GamepadHome");
+    assert_eq!(button_code(17), "GamepadButton17");
+}
+
+#[test]
+fn held_buttons_press_and_release_their_keys() {
+    let mut input = Input::new();
+    let frame = |input: &mut Input, events: Vec<InputEvent>| {
+        input.flush_pressed_map();
+        for event in events {
+            input.push_event(event);
+        }
+        input.poll_events(1.0 / 60.0);
+    };
+    let button = |pad, value| InputEvent::GamepadButton {
+        pad,
+        button: 0,
+        value,
+    };
+
+    frame(
+        &mut input,
+        vec![
+            InputEvent::GamepadConnected { pad: 0 },
+            InputEvent::GamepadConnected { pad: 1 },
+            button(0, 1.0),
+        ],
+    );
+    assert!(input.key_was_pressed("GamepadA"));
+    assert!(input.action_pressed("grow"));
+
+    // A second pad holding it too changes nothing.
+    frame(&mut input, vec![button(1, 1.0)]);
+    assert!(input.get_key_down("GamepadA"));
+    assert!(!input.key_was_pressed("GamepadA"));
+
+    frame(&mut input, vec![button(0, 0.0)]);
+    assert!(input.get_key_down("GamepadA"));
+    assert!(!input.key_was_released("GamepadA"));
+
+    // Under the threshold counts as let go.
+    frame(&mut input, vec![button(1, 0.4)]);
+    assert!(!input.get_key_down("GamepadA"));
+    assert!(input.key_was_released("GamepadA"));
+
+    // Unplugging a pad releases what it held.
+    frame(&mut input, vec![button(1, 1.0)]);
+    assert!(input.key_was_pressed("GamepadA"));
+    frame(&mut input, vec![InputEvent::GamepadDisconnected { pad: 1 }]);
+    assert!(input.key_was_released("GamepadA"));
+}
+
+#[test]
+fn sticks_and_triggers_become_analog_sources() {
+    let mut input = Input::new();
+    input.push_event(InputEvent::GamepadConnected { pad: 0 });
+    input.push_event(InputEvent::GamepadAxis {
+        pad: 0,
+        axis: 0,
+        value: -0.6,
+    });
+    input.push_event(InputEvent::GamepadButton {
+        pad: 0,
+        button: RIGHT_TRIGGER,
+        value: 1.0,
+    });
+    input.poll_events(1.0 / 60.0);
+
+    assert!(close(input.analog_value("GamepadLeftX"), -0.5));
+    assert_eq!(input.analog_value("GamepadLeftY"), 0.0);
+    assert_eq!(input.analog_value("GamepadRightTrigger"), 1.0);
+    assert!(input.action_down("fire"));
+}
@@ -0,0 +1,172 @@
+use arcamedia_engine::engine::game_loop::GameLoop;
+use arcamedia_engine::engine::object::rect::Rect;
+use arcamedia_engine::engine::object::snake_game::SnakeGame;
+use arcamedia_engine::engine::object::textured_rect::TexturedRect;
+use arcamedia_engine::engine::object_manager::ObjectManager;
+use arcamedia_engine::engine::renderer::headless::{
+    DrawCommand, HeadlessRenderer, RenderCommand, SpriteCommand,
+};
+use arcamedia_engine::engine::renderer::{Renderer, Uniform, VertexLayout};
+use arcamedia_engine::engine::sprite_batch::FULL_UV;
+use arcamedia_engine::engine::texture::Texture;
+use arcamedia_engine::engine::transform::Transform;
+use arcamedia_engine::engine::Engine;
+use arcamedia_engine::object;
+use image::RgbaImage;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+#[test]
+fn renderer_records_clears_and_draws() {
+    let mut renderer = HeadlessRenderer::new();
+    let commands = renderer.commands();
+
+    let colored = renderer.create_shader("", "").unwrap();
+    let textured = renderer.create_shader("", "").unwrap();
+    let quad = renderer.create_mesh(&[0.0; 12], VertexLayout::Position);
+    let textured_quad = renderer.create_mesh(&[0.0; 24], VertexLayout::PositionTexCoord);
+    let texture = renderer.create_texture(&RgbaImage::new(2, 2));
+    let transform = Transform::new([10.0, 20.0].into(), [30.0, 40.0].into(), 0.5);
+
+    renderer.clear([0.0, 0.0, 0.0, 1.0]);
+    renderer.bind_shader(colored);
+    renderer.uniform(colored, "fragColor", Uniform::Vec4([1.0, 0.0, 0.0, 1.0]));
+    renderer.uniform_transform(colored, &transform);
+    renderer.draw_mesh(quad);
+
+    // Uniforms stay with their shader across binds.
+    renderer.bind_shader(textured);
+    renderer.bind_texture(texture);
+    renderer.draw_mesh(textured_quad);
+    renderer.bind_shader(colored);
+    renderer.draw_mesh(quad);
+
+    assert_eq!(
+        *commands.borrow(),
+        vec![
+            RenderCommand::Clear([0.0, 0.0, 0.0, 1.0]),
+            RenderCommand::Draw(DrawCommand {
+                shader: colored,
+                transform: Some(transform.clone()),
+                color: Some([1.0, 0.0, 0.0, 1.0]),
+                texture: None,
+                vertex_count: 6,
+            }),
+            RenderCommand::Draw(DrawCommand {
+                shader: textured,
+                transform: None,
+                color: None,
+                texture: Some(texture),
+                vertex_count: 6,
+            }),
+            RenderCommand::Draw(DrawCommand {
+                shader: colored,
+                transform: Some(transform),
+                color: Some([1.0, 0.0, 0.0, 1.0]),
+                texture: Some(texture),
+                vertex_count: 6,
+            }),
+        ]
+    );
+}
+
+#[test]
+fn small_scene_records_one_command_per_rect() {
+    let renderer = HeadlessRenderer::new();
+    let commands = renderer.commands();
+    let mut ctx = Engine::init_headless(renderer);
+
+    let texture = Texture::from_image(ctx.renderer.as_mut(), &RgbaImage::new(2, 2));
+    let mut textured_rect = TexturedRect::new(
+        [200.0, -50.0].into(),
+        [64.0, 64.0].into(),
+        0.5,
+        [1.0, 1.0, 1.0, 1.0],
+        texture,
+        false,
+    );
+    textured_rect.uv = [0.0, 0.0, 0.5, 0.5];
+
+    object!(
+        ctx,
+        Rect::new(
+            [0.0, 0.0].into(),
+            [100.0, 50.0].into(),
+            0.0,
+            [1.0, 0.0, 0.0, 1.0],
+            false,
+        )
+    );
+    object!(ctx, textured_rect);
+    // Far enough away to be culled.
+    object!(
+        ctx,
+        Rect::new(
+            [5000.0, 0.0].into(),
+            [10.0, 10.0].into(),
+            0.0,
+            [0.0, 1.0, 0.0, 1.0],
+            false,
+        )
+    );
+    ObjectManager::apply_pending(&mut ctx);
+
+    let mut game_loop = GameLoop::new(60.0);
+    game_loop.step_once(&mut ctx);
+    game_loop.render(&mut ctx, 0.0);
+
+    assert_eq!(
+        *commands.borrow(),
+        vec![
+            RenderCommand::Clear([0.0, 0.0, 0.0, 1.0]),
+            RenderCommand::Sprite(SpriteCommand {
+                shader: 0,
+                transform: Transform::new([0.0, 0.0].into(), [100.0, 50.0].into(), 0.0),
+                color: [1.0, 0.0, 0.0, 1.0],
+                texture: Some(0),
+                uv: FULL_UV,
+            }),
+            RenderCommand::Sprite(SpriteCommand {
+                shader: 0,
+                transform: Transform::new([200.0, -50.0].into(), [64.0, 64.0].into(), 0.5),
+                color: [1.0, 1.0, 1.0, 1.0],
+                texture: Some(1),
+                uv: [0.0, 0.0, 0.5, 0.5],
+            }),
+        ]
+    );
+}
+
+#[test]
+fn snake_game_draws_head_walls_and_buttons() {
+    let renderer = HeadlessRenderer::new();
+    let commands = renderer.commands();
+    let mut ctx = Engine::init_headless(renderer);
+
+    let game = async_std::task::block_on(SnakeGame::new(&mut ctx, [1500.0, 900.0].into()));
+    object!(ctx, game);
+    ObjectManager::apply_pending(&mut ctx);
+
+    let mut game_loop = GameLoop::new(60.0);
+    game_loop.step_once(&mut ctx);
+    game_loop.render(&mut ctx, 0.0);
+
+    let sprites: Vec<SpriteCommand> = commands
+        .borrow()
+        .iter()
+        .filter_map(|command| match command {
+            RenderCommand::Sprite(sprite) => Some(sprite.clone()),
+            _ => None,
+        })
+        .collect();
+
+    // The head first, as the game was spawned first, then the four walls
+    // and the exit and start buttons.
+    assert_eq!(sprites.len(), 7);
+    assert_eq!(sprites[0].transform.position, [0.0, 0.0].into());
+    assert_ne!(sprites[0].texture, sprites[1].texture);
+    assert_eq!(sprites[1].transform.position, [0.0, -450.0].into());
+    assert_eq!(sprites[4].transform.position, [750.0, 0.0].into());
+    assert_eq!(sprites[5].color, [1.0, 0.0, 0.0, 1.0]);
+    assert_eq!(sprites[6].color, [0.0, 1.0, 0.0, 1.0]);
+}
@@ -0,0 +1,199 @@
+use arcamedia_engine::engine::context::EngineContext;
+use arcamedia_engine::engine::object::player::Player;
+use arcamedia_engine::engine::object::rect::Rect;
+use arcamedia_engine::engine::object_manager::{ObjectId, ObjectManager};
+use arcamedia_engine::engine::renderer::headless::HeadlessRenderer;
+use arcamedia_engine::engine::texture::Texture;
+use arcamedia_engine::engine::transform::Transform;
+use arcamedia_engine::engine::Engine;
+use arcamedia_engine::object;
+use image::RgbaImage;
+use std::cell::RefCell;
+use std::f64::consts::FRAC_PI_2;
+use std::rc::Rc;
+
+fn rect(ctx: &mut EngineContext, position: [f64; 2], size: [f64; 2], rotation: f64) -> ObjectId {
+    object!(
+        ctx,
+        Rect::new(
+            position.into(),
+            size.into(),
+            rotation,
+            [1.0, 1.0, 1.0, 1.0],
+            false,
+        )
+    )
+}
+
+fn transform(ctx: &EngineContext, id: ObjectId) -> Transform {
+    ctx.om
+        .get(id)
+        .unwrap()
+        .borrow()
+        .transform()
+        .unwrap()
+        .clone()
+}
+
+fn assert_near(actual: &Transform, position: [f64; 2], size: [f64; 2], rotation: f64) {
+    let close = |a: f64, b: f64| (a - b).abs() < 1e-9;
+    assert!(
+        close(actual.position.x, position[0])
+            && close(actual.position.y, position[1])
+            && close(actual.size.x, size[0])
+            && close(actual.size.y, size[1])
+            && close(actual.rotation, rotation),
+        "{actual:?}"
+    );
+}
+
+#[test]
+fn children_are_placed_by_position_rotation_and_scale() {
+    let mut ctx = Engine::init_headless(HeadlessRenderer::new());
+
+    let parent = rect(&mut ctx, [100.0, 0.0], [50.0, 50.0], FRAC_PI_2);
+    let child = rect(&mut ctx, [10.0, 0.0], [4.0, 4.0], 0.1);
+    let grandchild = rect(&mut ctx, [0.0, 5.0], [1.0, 1.0], 0.0);
+    ctx.om.set_parent(child, Some(parent)).unwrap();
+    ctx.om.set_parent(grandchild, Some(child)).unwrap();
+    ctx.om.set_scale(parent, [2.0, 2.0].into());
+    ctx.om.set_scale(child, [1.0, 3.0].into());
+
+    ObjectManager::apply_pending(&mut ctx);
+    ObjectManager::tick(&mut ctx, 0.0);
+
+    // The parent keeps its own size, and its rotation turns the offset.
+    assert_near(
+        &transform(&ctx, parent),
+        [100.0, 0.0],
+        [50.0, 50.0],
+        FRAC_PI_2,
+    );
+    assert_near(
+        &transform(&ctx, child),
+        [100.0, 20.0],
+        [8.0, 8.0],
+        FRAC_PI_2 + 0.1,
+    );
+    let turned = (FRAC_PI_2 + 0.1).sin() * 30.0;
+    let along = (FRAC_PI_2 + 0.1).cos() * 30.0;
+    assert_near(
+        &transform(&ctx, grandchild),
+        [100.0 - turned, 20.0 + along],
+        [2.0, 6.0],
+        FRAC_PI_2 + 0.1,
+    );
+
+    // Moving the parent carries the child, and the child moves through its
+    // local transform.
+    ctx.om
+        .get(parent)
+        .unwrap()
+        .borrow_mut()
+        .transform_mut()
+        .unwrap()
+        .position = [0.0, 0.0].into();
+    ctx.om.local_transform_mut(child).unwrap().position = [0.0, -40.0].into();
+    assert!(ctx.om.local_transform_mut(parent).is_none());
+    ObjectManager::tick(&mut ctx, 0.0);
+
+    assert_near(
+        &transform(&ctx, child),
+        [80.0, 0.0],
+        [8.0, 8.0],
+        FRAC_PI_2 + 0.1,
+    );
+    assert_eq!(
+        ctx.om
+            .objects_in_bounds(&Transform::new([80.0, 0.0].into(), [1.0, 1.0].into(), 0.0)),
+        vec![child]
+    );
+}
+
+#[test]
+fn parenting_rejects_cycles_and_stale_handles() {
+    let mut ctx = Engine::init_headless(HeadlessRenderer::new());
+
+    let a = rect(&mut ctx, [0.0, 0.0], [1.0, 1.0], 0.0);
+    let b = rect(&mut ctx, [0.0, 0.0], [1.0, 1.0], 0.0);
+    let c = rect(&mut ctx, [0.0, 0.0], [1.0, 1.0], 0.0);
+    ObjectManager::apply_pending(&mut ctx);
+
+    ctx.om.set_parent(b, Some(a)).unwrap();
+    ctx.om.set_parent(c, Some(b)).unwrap();
+
+    assert!(ctx.om.set_parent(a, Some(c)).is_err());
+    assert!(ctx.om.set_parent(a, Some(a)).is_err());
+    assert_eq!(ctx.om.parent(a), None);
+    assert_eq!(ctx.om.children(a), &[b]);
+
+    // Moving `c` up a level takes it out of `b`'s children.
+    ctx.om.set_parent(c, Some(a)).unwrap();
+    assert_eq!(ctx.om.children(a), &[b, c]);
+    assert!(ctx.om.children(b).is_empty());
+
+    ctx.om.set_parent(c, None).unwrap();
+    assert_eq!(ctx.om.parent(c), None);
+    assert_eq!(ctx.om.children(a), &[b]);
+
+    ctx.om.despawn(c);
+    ObjectManager::apply_pending(&mut ctx);
+    assert!(ctx.om.set_parent(c, Some(a)).is_err());
+    assert!(ctx.om.set_parent(a, Some(c)).is_err());
+}
+
+#[test]
+fn despawning_a_parent_despawns_its_children() {
+    let mut ctx = Engine::init_headless(HeadlessRenderer::new());
+
+    // The first object spawned is never despawned.
+    rect(&mut ctx, [0.0, 0.0], [1.0, 1.0], 0.0);
+    let parent = rect(&mut ctx, [0.0, 0.0], [1.0, 1.0], 0.0);
+    let child = rect(&mut ctx, [0.0, 0.0], [1.0, 1.0], 0.0);
+    let grandchild = rect(&mut ctx, [0.0, 0.0], [1.0, 1.0], 0.0);
+    let sibling = rect(&mut ctx, [0.0, 0.0], [1.0, 1.0], 0.0);
+    ctx.om.set_parent(child, Some(parent)).unwrap();
+    ctx.om.set_parent(grandchild, Some(child)).unwrap();
+    ObjectManager::apply_pending(&mut ctx);
+
+    ctx.om.despawn(parent);
+    ObjectManager::apply_pending(&mut ctx);
+
+    assert!(!ctx.om.contains(parent));
+    assert!(!ctx.om.contains(child));
+    assert!(!ctx.om.contains(grandchild));
+    assert!(ctx.om.contains(sibling));
+}
+
+#[test]
+fn player_aim_follows_the_player() {
+    let mut ctx = Engine::init_headless(HeadlessRenderer::new());
+    let texture = Texture::from_image(ctx.renderer.as_mut(), &RgbaImage::new(1, 1));
+    // The first object spawned is never despawned.
+    rect(&mut ctx, [0.0, 0.0], [1.0, 1.0], 0.0);
+
+    let player = Player::new(
+        [100.0, 50.0].into(),
+        [40.0, 40.0].into(),
+        0.0,
+        [1.0, 1.0, 1.0, 1.0],
+        texture,
+    )
+    .spawn(&mut ctx);
+    ObjectManager::apply_pending(&mut ctx);
+    let aim = ctx.om.children(player)[0];
+
+    ObjectManager::tick(&mut ctx, 0.0);
+    assert_near(&transform(&ctx, aim), [350.0, 50.0], [500.0, 5.0], 0.0);
+
+    // Moved right by the physics step, with the aim carried along.
+    ctx.input.set_virtual_key("KeyD", true);
+    ctx.input.poll_events(0.1);
+    ObjectManager::tick(&mut ctx, 0.1);
+    assert_near(&transform(&ctx, player), [200.0, 50.0], [40.0, 40.0], 0.0);
+    assert_near(&transform(&ctx, aim), [450.0, 50.0], [500.0, 5.0], 0.0);
+
+    ctx.om.despawn(player);
+    ObjectManager::apply_pending(&mut ctx);
+    assert!(!ctx.om.contains(aim));
+}
@@ -0,0 +1,96 @@
+use arcamedia_engine::engine::layer::{CollisionLayers, LayerMask};
+use arcamedia_engine::engine::object::rect::Rect;
+use arcamedia_engine::engine::object_manager::ObjectManager;
+use arcamedia_engine::engine::renderer::headless::HeadlessRenderer;
+use arcamedia_engine::engine::Engine;
+use arcamedia_engine::object;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+#[test]
+fn every_layer_collides_until_told_otherwise() {
+    let mut layers = CollisionLayers::new();
+    assert!(layers.collides(LayerMask::PLAYER, LayerMask::WALL));
+    assert_eq!(layers.collision_mask(LayerMask::UI), LayerMask::ALL);
+
+    layers.set_collides(LayerMask::PLAYER, LayerMask::PICKUP, false);
+    assert!(!layers.collides(LayerMask::PLAYER, LayerMask::PICKUP));
+    assert!(!layers.collides(LayerMask::PICKUP, LayerMask::PLAYER));
+    assert!(layers.collides(LayerMask::PLAYER, LayerMask::WALL));
+    assert!(layers.collides(LayerMask::PICKUP, LayerMask::PICKUP));
+    assert_eq!(
+        layers.collision_mask(LayerMask::PLAYER),
+        LayerMask::ALL.without(LayerMask::PICKUP)
+    );
+
+    layers.set_collides(LayerMask::PICKUP, LayerMask::PLAYER, true);
+    assert!(layers.collides(LayerMask::PLAYER, LayerMask::PICKUP));
+}
+
+#[test]
+fn masks_of_several_layers_set_and_test_them_all() {
+    let mut layers = CollisionLayers::new();
+    let ghosts = LayerMask::PLAYER | LayerMask::PICKUP;
+
+    layers.set_collides(ghosts, LayerMask::WALL | LayerMask::UI, false);
+    for a in [LayerMask::PLAYER, LayerMask::PICKUP] {
+        for b in [LayerMask::WALL, LayerMask::UI] {
+            assert!(!layers.collides(a, b));
+            assert!(!layers.collides(b, a));
+        }
+    }
+    assert!(layers.collides(LayerMask::WALL, LayerMask::UI));
+
+    // Any pair colliding is enough.
+    assert!(layers.collides(ghosts, LayerMask::WALL | LayerMask::DEFAULT));
+    assert!(!layers.collides(ghosts, LayerMask::NONE));
+}
+
+#[test]
+fn named_layers_take_the_free_bits() {
+    let mut layers = CollisionLayers::new();
+    assert_eq!(layers.get("wall"), Some(LayerMask::WALL));
+    assert_eq!(layers.get("water"), None);
+
+    let water = layers.layer("water").unwrap();
+    assert_eq!(water, LayerMask(1 << 5));
+    assert_eq!(layers.layer("water"), Ok(water));
+    assert_eq!(layers.get("water"), Some(water));
+
+    for index in 6..32 {
+        assert_eq!(
+            layers.layer(&format!("layer{index}")),
+            Ok(LayerMask(1 << index))
+        );
+    }
+    assert!(layers.layer("one too many").is_err());
+    assert_eq!(layers.layer("layer31"), Ok(LayerMask(1 << 31)));
+}
+
+#[test]
+fn objects_on_layers_that_do_not_collide_make_no_pairs() {
+    let mut ctx = Engine::init_headless(HeadlessRenderer::new());
+    let rect = |layer| {
+        let mut rect = Rect::new(
+            [0.0, 0.0].into(),
+            [10.0, 10.0].into(),
+            0.0,
+            [1.0, 1.0, 1.0, 1.0],
+            true,
+        );
+        rect.layer = layer;
+        rect
+    };
+    object!(ctx, rect(LayerMask::WALL));
+    object!(ctx, rect(LayerMask::PLAYER));
+    object!(ctx, rect(LayerMask::PICKUP));
+    ObjectManager::apply_pending(&mut ctx);
+    assert_eq!(ctx.om.collisions().len(), 3);
+
+    ctx.om.layers.set_collides(
+        LayerMask::PLAYER,
+        LayerMask::PICKUP | LayerMask::WALL,
+        false,
+    );
+    assert_eq!(ctx.om.collisions().len(), 1);
+}
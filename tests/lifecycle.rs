@@ -0,0 +1,183 @@
+use arcamedia_engine::engine::context::EngineContext;
+use arcamedia_engine::engine::object::Object;
+use arcamedia_engine::engine::object_manager::{ObjectId, ObjectManager};
+use arcamedia_engine::engine::renderer::headless::HeadlessRenderer;
+use arcamedia_engine::engine::tag::Tag;
+use arcamedia_engine::engine::Engine;
+use arcamedia_engine::object;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+type Log = Rc<RefCell<Vec<String>>>;
+type Hook = Box<dyn FnOnce(&mut EngineContext)>;
+
+// Logs its `init` and `tick` calls, running `on_init` and `on_tick` the
+// first time each happens.
+struct Actor {
+    name: &'static str,
+    log: Log,
+    on_init: Option<Hook>,
+    on_tick: Option<Hook>,
+    tags: Vec<Tag>,
+}
+
+impl Object for Actor {
+    fn init(&mut self, ctx: &mut EngineContext) {
+        self.log.borrow_mut().push(format!("init {}", self.name));
+        if let Some(hook) = self.on_init.take() {
+            hook(ctx);
+        }
+    }
+
+    fn tick(&mut self, ctx: &mut EngineContext, _delta_time: f64) {
+        self.log.borrow_mut().push(format!("tick {}", self.name));
+        if let Some(hook) = self.on_tick.take() {
+            hook(ctx);
+        }
+    }
+
+    fn tags(&self) -> &Vec<Tag> {
+        &self.tags
+    }
+
+    fn tags_mut(&mut self) -> &mut Vec<Tag> {
+        &mut self.tags
+    }
+}
+
+fn actor(name: &'static str, log: &Log) -> Actor {
+    Actor {
+        name,
+        log: log.clone(),
+        on_init: None,
+        on_tick: None,
+        tags: Vec::new(),
+    }
+}
+
+fn step(ctx: &mut EngineContext) {
+    ObjectManager::tick(ctx, 1.0 / 60.0);
+    ObjectManager::apply_pending(ctx);
+}
+
+fn take(log: &Log) -> Vec<String> {
+    std::mem::take(&mut *log.borrow_mut())
+}
+
+#[test]
+fn spawns_during_tick_go_live_at_apply_pending() {
+    let mut ctx = Engine::init_headless(HeadlessRenderer::new());
+    let log = Log::default();
+    let spawned = Rc::new(RefCell::new(None));
+
+    let mut root = actor("root", &log);
+    let (hook_log, hook_spawned) = (log.clone(), spawned.clone());
+    root.on_tick = Some(Box::new(move |ctx: &mut EngineContext| {
+        // Spawned from `init` in turn, and live in the same `apply_pending`.
+        let mut child = actor("child", &hook_log);
+        let grandchild_log = hook_log.clone();
+        child.on_init = Some(Box::new(move |ctx: &mut EngineContext| {
+            object!(ctx, actor("grandchild", &grandchild_log));
+        }));
+
+        let id = object!(ctx, child);
+        assert!(!ctx.om.contains(id));
+        assert!(ctx.om.get(id).is_none());
+        *hook_spawned.borrow_mut() = Some(id);
+    }));
+    object!(ctx, root);
+    ObjectManager::apply_pending(&mut ctx);
+    assert_eq!(take(&log), ["init root"]);
+
+    ObjectManager::tick(&mut ctx, 1.0 / 60.0);
+    let child: ObjectId = spawned.borrow().unwrap();
+    assert_eq!(take(&log), ["tick root"]);
+    assert!(!ctx.om.contains(child));
+
+    ObjectManager::apply_pending(&mut ctx);
+    assert_eq!(take(&log), ["init child", "init grandchild"]);
+    assert!(ctx.om.contains(child));
+
+    step(&mut ctx);
+    assert_eq!(take(&log), ["tick root", "tick child", "tick grandchild"]);
+}
+
+#[test]
+fn despawns_during_tick_take_effect_at_apply_pending() {
+    let mut ctx = Engine::init_headless(HeadlessRenderer::new());
+    let log = Log::default();
+
+    object!(ctx, actor("root", &log));
+    let mut first = actor("first", &log);
+    let victim = Rc::new(RefCell::new(None));
+    let hook_victim = victim.clone();
+    first.on_tick = Some(Box::new(move |ctx: &mut EngineContext| {
+        let id = hook_victim.borrow().unwrap();
+        ctx.om.despawn(id);
+        // Still there until the step is over.
+        assert!(ctx.om.contains(id));
+    }));
+    object!(ctx, first);
+    *victim.borrow_mut() = Some(object!(ctx, actor("second", &log)));
+    ObjectManager::apply_pending(&mut ctx);
+    take(&log);
+
+    ObjectManager::tick(&mut ctx, 1.0 / 60.0);
+    assert_eq!(take(&log), ["tick root", "tick first", "tick second"]);
+
+    ObjectManager::apply_pending(&mut ctx);
+    let victim = victim.borrow().unwrap();
+    assert!(!ctx.om.contains(victim));
+
+    step(&mut ctx);
+    assert_eq!(take(&log), ["tick root", "tick first"]);
+}
+
+#[test]
+fn objects_tick_by_priority_then_spawn_order() {
+    let mut ctx = Engine::init_headless(HeadlessRenderer::new());
+    let log = Log::default();
+
+    let root = object!(ctx, actor("root", &log));
+    let a = object!(ctx, actor("a", &log));
+    let b = object!(ctx, actor("b", &log));
+    let c = object!(ctx, actor("c", &log));
+    // Set before they are live.
+    ctx.om.set_tick_priority(c, 5);
+    ctx.om.set_tick_priority(root, -1);
+    ObjectManager::apply_pending(&mut ctx);
+    take(&log);
+
+    step(&mut ctx);
+    assert_eq!(take(&log), ["tick c", "tick a", "tick b", "tick root"]);
+
+    ctx.om.set_tick_priority(b, 5);
+    ctx.om.set_tick_priority(a, 10);
+    step(&mut ctx);
+    assert_eq!(take(&log), ["tick a", "tick b", "tick c", "tick root"]);
+}
+
+#[test]
+fn disabled_objects_skip_tick() {
+    let mut ctx = Engine::init_headless(HeadlessRenderer::new());
+    let log = Log::default();
+
+    object!(ctx, actor("root", &log));
+    let wall = object!(ctx, actor("wall", &log));
+    ctx.om.set_tick_enabled(wall, false);
+    object!(ctx, actor("mover", &log));
+    ObjectManager::apply_pending(&mut ctx);
+
+    // Still initialized, just never ticked.
+    assert_eq!(take(&log), ["init root", "init wall", "init mover"]);
+    step(&mut ctx);
+    step(&mut ctx);
+    assert_eq!(
+        take(&log),
+        ["tick root", "tick mover", "tick root", "tick mover"]
+    );
+
+    ctx.om.set_tick_enabled(wall, true);
+    step(&mut ctx);
+    assert_eq!(take(&log), ["tick root", "tick wall", "tick mover"]);
+}
@@ -0,0 +1,116 @@
+use arcamedia_engine::engine::context::EngineContext;
+use arcamedia_engine::engine::object::rect::Rect;
+use arcamedia_engine::engine::object_manager::{ObjectId, ObjectManager};
+use arcamedia_engine::engine::renderer::headless::HeadlessRenderer;
+use arcamedia_engine::engine::Engine;
+use arcamedia_engine::object;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+fn rect(ctx: &mut EngineContext, x: f64) -> ObjectId {
+    object!(
+        ctx,
+        Rect::new(
+            [x, 0.0].into(),
+            [10.0, 10.0].into(),
+            0.0,
+            [1.0, 1.0, 1.0, 1.0],
+            true,
+        )
+    )
+}
+
+#[test]
+fn handle_goes_stale_when_its_slot_is_reused() {
+    let mut ctx = Engine::init_headless(HeadlessRenderer::new());
+
+    rect(&mut ctx, 0.0);
+    let old = rect(&mut ctx, 100.0);
+    ObjectManager::apply_pending(&mut ctx);
+    assert!(ctx.om.contains(old));
+
+    ctx.om.despawn(old);
+    ObjectManager::apply_pending(&mut ctx);
+    assert!(!ctx.om.contains(old));
+    assert!(ctx.om.get(old).is_none());
+
+    // The freed slot is handed out again under a new generation, and only
+    // resolves once live.
+    let new = rect(&mut ctx, 200.0);
+    assert_ne!(new, old);
+    assert!(!ctx.om.contains(new));
+    ObjectManager::apply_pending(&mut ctx);
+    assert!(ctx.om.contains(new));
+    assert!(!ctx.om.contains(old));
+    assert_eq!(ctx.om.objects().count(), 2);
+
+    // Nothing done through the old handle reaches the new object.
+    ctx.om.despawn(old);
+    ctx.om.set_tick_enabled(old, false);
+    ObjectManager::apply_pending(&mut ctx);
+    assert!(ctx.om.contains(new));
+    assert_eq!(
+        ctx.om
+            .get(new)
+            .unwrap()
+            .borrow()
+            .transform()
+            .unwrap()
+            .position
+            .x,
+        200.0
+    );
+}
+
+#[test]
+fn despawned_before_going_live_never_spawns() {
+    let mut ctx = Engine::init_headless(HeadlessRenderer::new());
+
+    rect(&mut ctx, 0.0);
+    let id = rect(&mut ctx, 0.0);
+    ctx.om.despawn(id);
+    ObjectManager::apply_pending(&mut ctx);
+
+    assert!(!ctx.om.contains(id));
+    assert_eq!(ctx.om.objects().count(), 1);
+}
+
+#[test]
+fn reused_slots_keep_spawn_order() {
+    let mut ctx = Engine::init_headless(HeadlessRenderer::new());
+
+    let root = rect(&mut ctx, 0.0);
+    let first = rect(&mut ctx, 0.0);
+    let second = rect(&mut ctx, 0.0);
+    ObjectManager::apply_pending(&mut ctx);
+    ctx.om.despawn(first);
+    ObjectManager::apply_pending(&mut ctx);
+
+    // Takes `first`'s slot, ahead of `second`, but was spawned after it.
+    let third = rect(&mut ctx, 0.0);
+    ObjectManager::apply_pending(&mut ctx);
+    ObjectManager::tick(&mut ctx, 0.0);
+
+    assert_eq!(ctx.om.objects_on_screen, vec![root, second, third]);
+    assert_eq!(ctx.om.pick(&[0.0, 0.0].into()), Some(third));
+
+    // Pairs stay in id order, lower id first.
+    let pairs: Vec<(ObjectId, ObjectId)> = ctx
+        .om
+        .collisions()
+        .into_iter()
+        .map(|(a, b, _)| (a, b))
+        .collect();
+    assert_eq!(pairs, vec![(root, third), (root, second), (third, second)]);
+}
+
+#[cfg(debug_assertions)]
+#[test]
+#[should_panic(expected = "The root object can't be despawned")]
+fn despawning_the_root_is_caught() {
+    let mut ctx = Engine::init_headless(HeadlessRenderer::new());
+
+    let root = rect(&mut ctx, 0.0);
+    ObjectManager::apply_pending(&mut ctx);
+    ctx.om.despawn(root);
+}
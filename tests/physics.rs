@@ -0,0 +1,239 @@
+use arcamedia_engine::engine::collision::Collision;
+use arcamedia_engine::engine::context::EngineContext;
+use arcamedia_engine::engine::object::rect::Rect;
+use arcamedia_engine::engine::object::Object;
+use arcamedia_engine::engine::object_manager::{ObjectId, ObjectManager};
+use arcamedia_engine::engine::physics::RigidBody;
+use arcamedia_engine::engine::renderer::headless::HeadlessRenderer;
+use arcamedia_engine::engine::tag::Tag;
+use arcamedia_engine::engine::transform::Transform;
+use arcamedia_engine::engine::vec2f::Vec2f;
+use arcamedia_engine::engine::Engine;
+use arcamedia_engine::object;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+struct Body {
+    transform: Transform,
+    body: RigidBody,
+    tags: Vec<Tag>,
+    entered: Vec<ObjectId>,
+}
+
+impl Body {
+    fn new(position: [f64; 2], size: [f64; 2], body: RigidBody) -> Self {
+        Self {
+            transform: Transform::new(position.into(), size.into(), 0.0),
+            body,
+            tags: Vec::new(),
+            entered: Vec::new(),
+        }
+    }
+}
+
+impl Object for Body {
+    fn transform(&self) -> Option<&Transform> {
+        Some(&self.transform)
+    }
+
+    fn transform_mut(&mut self) -> Option<&mut Transform> {
+        Some(&mut self.transform)
+    }
+
+    fn body(&self) -> Option<&RigidBody> {
+        Some(&self.body)
+    }
+
+    fn body_mut(&mut self) -> Option<&mut RigidBody> {
+        Some(&mut self.body)
+    }
+
+    fn collides(&self) -> bool {
+        true
+    }
+
+    fn on_collision_enter(&mut self, _ctx: &mut EngineContext, collision: &Collision) {
+        self.entered.push(collision.other);
+    }
+
+    fn tags(&self) -> &Vec<Tag> {
+        &self.tags
+    }
+
+    fn tags_mut(&mut self) -> &mut Vec<Tag> {
+        &mut self.tags
+    }
+}
+
+fn wall(ctx: &mut EngineContext, position: [f64; 2], size: [f64; 2]) -> ObjectId {
+    object!(
+        ctx,
+        Rect::new(
+            position.into(),
+            size.into(),
+            0.0,
+            [1.0, 1.0, 1.0, 1.0],
+            true,
+        )
+    )
+}
+
+// The position and velocity of the body `id`.
+fn state(ctx: &EngineContext, id: ObjectId) -> (Vec2f, Vec2f) {
+    let object = ctx.om.get(id).unwrap();
+    let object = object.borrow();
+
+    (
+        object.transform().unwrap().position.clone(),
+        object.body().unwrap().velocity.clone(),
+    )
+}
+
+fn step(ctx: &mut EngineContext, delta_time: f64, steps: usize) {
+    ObjectManager::apply_pending(ctx);
+    for _ in 0..steps {
+        ObjectManager::tick(ctx, delta_time);
+    }
+}
+
+#[test]
+fn dynamic_body_settles_on_a_static_floor() {
+    let mut ctx = Engine::init_headless(HeadlessRenderer::new());
+    ctx.om.physics.gravity = [0.0, -1000.0].into();
+
+    wall(&mut ctx, [0.0, -50.0], [400.0, 100.0]);
+    let body = object!(
+        ctx,
+        Body::new(
+            [0.0, 100.0],
+            [10.0, 10.0],
+            RigidBody {
+                restitution: 0.5,
+                ..RigidBody::dynamic()
+            },
+        )
+    );
+
+    step(&mut ctx, 1.0 / 60.0, 180);
+
+    let (position, velocity) = state(&ctx, body);
+    assert!((position.y - 5.0).abs() < 0.5, "{position:?}");
+    assert!(velocity.y.abs() < 1e-9, "{velocity:?}");
+    assert_eq!(position.x, 0.0);
+}
+
+#[test]
+fn fast_impact_bounces_and_slow_one_does_not() {
+    for (speed, bounce) in [(600.0, 480.0), (30.0, 0.0)] {
+        let mut ctx = Engine::init_headless(HeadlessRenderer::new());
+
+        wall(&mut ctx, [0.0, -50.0], [400.0, 100.0]);
+        let body = object!(
+            ctx,
+            Body::new(
+                [0.0, 5.2],
+                [10.0, 10.0],
+                RigidBody {
+                    velocity: [0.0, -speed].into(),
+                    restitution: 0.8,
+                    ..RigidBody::dynamic()
+                },
+            )
+        );
+
+        step(&mut ctx, 1.0 / 60.0, 1);
+
+        let (position, velocity) = state(&ctx, body);
+        assert!((velocity.y - bounce).abs() < 1e-9, "{velocity:?}");
+        assert!(position.y > 4.9, "{position:?}");
+    }
+}
+
+#[test]
+fn body_slides_along_a_wall() {
+    for (friction, vertical_speed) in [(0.0, 100.0), (0.5, 50.0)] {
+        let mut ctx = Engine::init_headless(HeadlessRenderer::new());
+
+        object!(
+            ctx,
+            Body::new(
+                [20.0, 0.0],
+                [10.0, 400.0],
+                RigidBody {
+                    friction,
+                    ..RigidBody::fixed()
+                },
+            )
+        );
+        let body = object!(
+            ctx,
+            Body::new(
+                [9.0, 0.0],
+                [10.0, 10.0],
+                RigidBody {
+                    velocity: [100.0, 100.0].into(),
+                    friction,
+                    ..RigidBody::dynamic()
+                },
+            )
+        );
+
+        step(&mut ctx, 1.0 / 60.0, 1);
+
+        // Pushed back out of the wall, keeping only the speed along it less
+        // what friction took.
+        let (position, velocity) = state(&ctx, body);
+        assert!(velocity.x.abs() < 1e-9, "{velocity:?}");
+        assert!((velocity.y - vertical_speed).abs() < 1e-9, "{velocity:?}");
+        assert!((position.x - 10.0).abs() < 0.02, "{position:?}");
+    }
+}
+
+#[test]
+fn continuous_body_stops_at_a_thin_wall_through_a_hitch() {
+    for continuous in [true, false] {
+        let mut ctx = Engine::init_headless(HeadlessRenderer::new());
+
+        let wall = wall(&mut ctx, [95.0, 0.0], [10.0, 400.0]);
+        let body = Rc::new(RefCell::new(Body::new(
+            [0.0, 0.0],
+            [10.0, 10.0],
+            RigidBody {
+                velocity: [1000.0, 0.0].into(),
+                continuous,
+                ..RigidBody::dynamic()
+            },
+        )));
+        ctx.om.spawn(body.clone());
+
+        // A quarter second hitch moves it 250 px in one step.
+        step(&mut ctx, 0.25, 1);
+
+        let body = body.borrow();
+        let position = &body.transform.position;
+        if continuous {
+            assert!((position.x - 85.0).abs() < 0.01, "{position:?}");
+            assert_eq!(body.body.velocity, [0.0, 0.0].into());
+            assert_eq!(body.entered, vec![wall]);
+        } else {
+            assert_eq!(position.x, 250.0);
+            assert!(body.entered.is_empty());
+        }
+    }
+}
+
+#[test]
+fn transform_sweep_reports_time_and_normal() {
+    let mover = Transform::new([0.0, 0.0].into(), [10.0, 10.0].into(), 0.0);
+    let walls = [
+        Transform::new([95.0, 0.0].into(), [10.0, 400.0].into(), 0.0),
+        Transform::new([55.0, 0.0].into(), [10.0, 400.0].into(), 0.0),
+    ];
+
+    let hit = mover.sweep(&[250.0, 0.0].into(), &walls).unwrap();
+    assert_eq!(hit.index, 1);
+    assert!((hit.time - 45.0 / 250.0).abs() < 1e-9);
+    assert_eq!(hit.normal, [1.0, 0.0].into());
+
+    assert!(mover.sweep(&[0.0, 250.0].into(), &walls).is_none());
+}
@@ -0,0 +1,177 @@
+use arcamedia_engine::engine::context::EngineContext;
+use arcamedia_engine::engine::layer::LayerMask;
+use arcamedia_engine::engine::line_seg::LineSeg;
+use arcamedia_engine::engine::object::rect::Rect;
+use arcamedia_engine::engine::object_manager::{ObjectId, ObjectManager};
+use arcamedia_engine::engine::query::{QueryFilter, QueryHit};
+use arcamedia_engine::engine::raycast::Raycast;
+use arcamedia_engine::engine::renderer::headless::HeadlessRenderer;
+use arcamedia_engine::engine::shape::Collider;
+use arcamedia_engine::engine::vec2f::Vec2f;
+use arcamedia_engine::engine::Engine;
+use arcamedia_engine::object;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+fn close(a: &Vec2f, b: [f64; 2]) -> bool {
+    (a.x - b[0]).abs() < 1e-6 && (a.y - b[1]).abs() < 1e-6
+}
+
+// A 20x20 box at `x` on the x axis, on `layer`.
+fn crate_at(ctx: &mut EngineContext, x: f64, layer: LayerMask) -> ObjectId {
+    let mut rect = Rect::new(
+        [x, 0.0].into(),
+        [20.0, 20.0].into(),
+        0.0,
+        [1.0, 1.0, 1.0, 1.0],
+        true,
+    );
+    rect.layer = layer;
+    object!(ctx, rect)
+}
+
+// The root, then crates at x = 100, 50 and 200, in that spawn order.
+fn scene() -> (EngineContext, [ObjectId; 3]) {
+    let mut ctx = Engine::init_headless(HeadlessRenderer::new());
+    crate_at(&mut ctx, -1000.0, LayerMask::DEFAULT);
+    let far = crate_at(&mut ctx, 100.0, LayerMask::DEFAULT);
+    let near = crate_at(&mut ctx, 50.0, LayerMask::DEFAULT);
+    let wall = crate_at(&mut ctx, 200.0, LayerMask::WALL);
+    ObjectManager::apply_pending(&mut ctx);
+
+    (ctx, [near, far, wall])
+}
+
+fn objects(hits: &[QueryHit]) -> Vec<ObjectId> {
+    hits.iter().map(|hit| hit.object).collect()
+}
+
+#[test]
+fn ray_cast_all_hits_nearest_first_within_reach() {
+    let (ctx, [near, far, wall]) = scene();
+    let origin = Vec2f::new(0.0, 0.0);
+    let right = Vec2f::new(1.0, 0.0);
+
+    let hits = ctx
+        .om
+        .ray_cast_all(&origin, &right, 1000.0, &QueryFilter::default());
+    assert_eq!(objects(&hits), vec![near, far, wall]);
+    assert_eq!(hits[0].distance, 40.0);
+    assert!(close(&hits[0].pos, [40.0, 0.0]));
+    assert!(close(&hits[0].normal, [-1.0, 0.0]));
+    assert_eq!(hits[2].distance, 190.0);
+
+    // Out of reach.
+    let hits = ctx
+        .om
+        .ray_cast_all(&origin, &right, 100.0, &QueryFilter::default());
+    assert_eq!(objects(&hits), vec![near, far]);
+
+    // The direction's length doesn't matter, but it needs one.
+    let hits = ctx.om.ray_cast_all(
+        &origin,
+        &Vec2f::new(7.0, 0.0),
+        60.0,
+        &QueryFilter::default(),
+    );
+    assert_eq!(objects(&hits), vec![near]);
+    assert!(ctx
+        .om
+        .ray_cast_all(
+            &origin,
+            &Vec2f::new(0.0, 0.0),
+            1000.0,
+            &QueryFilter::default()
+        )
+        .is_empty());
+
+    // Going the other way misses them all.
+    let hits = ctx.om.ray_cast_all(
+        &origin,
+        &Vec2f::new(-1.0, 0.0),
+        500.0,
+        &QueryFilter::default(),
+    );
+    assert!(hits.is_empty());
+}
+
+#[test]
+fn ray_cast_all_skips_what_the_filter_rejects() {
+    let (ctx, [near, far, wall]) = scene();
+    let origin = Vec2f::new(0.0, 0.0);
+    let right = Vec2f::new(1.0, 0.0);
+
+    let filter = QueryFilter::new(LayerMask::WALL);
+    let hits = ctx.om.ray_cast_all(&origin, &right, 1000.0, &filter);
+    assert_eq!(objects(&hits), vec![wall]);
+
+    let filter = QueryFilter::default().excluding(near);
+    let hit = ctx.om.ray_cast(&origin, &right, 1000.0, &filter);
+    assert_eq!(hit.map(|hit| hit.object), Some(far));
+}
+
+#[test]
+fn ray_starting_inside_an_object_hits_it_at_zero() {
+    let (ctx, [near, far, _]) = scene();
+
+    let hits = ctx.om.ray_cast_all(
+        &Vec2f::new(50.0, 0.0),
+        &Vec2f::new(1.0, 0.0),
+        100.0,
+        &QueryFilter::default(),
+    );
+    assert_eq!(objects(&hits), vec![near, far]);
+    assert_eq!(hits[0].distance, 0.0);
+
+    // Raycast leaves it out by layer, as the player does itself.
+    let (mut ctx, [near, ..]) = scene();
+    let inside = crate_at(&mut ctx, 0.0, LayerMask::PLAYER);
+    ObjectManager::apply_pending(&mut ctx);
+
+    let ray = LineSeg::new([0.0, 0.0].into(), [300.0, 0.0].into());
+    let mut raycast = Raycast::new(ray, LayerMask::ALL);
+    raycast.fire(&ctx.om);
+    assert_eq!(raycast.hit.as_ref().map(|hit| hit.object), Some(inside));
+
+    raycast.set_mask(!LayerMask::PLAYER);
+    raycast.fire(&ctx.om);
+    let hit = raycast.hit.unwrap();
+    assert_eq!(hit.object, near);
+    assert_eq!(hit.distance, 40.0);
+}
+
+#[test]
+fn shape_cast_all_hits_where_the_shape_first_touches() {
+    let (ctx, [near, far, wall]) = scene();
+    let ball = Collider::circle([0.0, 0.0].into(), 5.0);
+    let right = Vec2f::new(1.0, 0.0);
+
+    let hits = ctx
+        .om
+        .shape_cast_all(&ball, &right, 1000.0, &QueryFilter::default());
+    assert_eq!(objects(&hits), vec![near, far, wall]);
+    assert!((hits[0].distance - 35.0).abs() < 1e-6);
+    assert!(close(&hits[0].pos, [35.0, 0.0]));
+    assert!(close(&hits[0].normal, [-1.0, 0.0]));
+
+    // Clipping the crates' tops still hits, passing over them doesn't.
+    let ball = Collider::circle([0.0, 14.0].into(), 5.0);
+    let hits = ctx
+        .om
+        .shape_cast_all(&ball, &right, 1000.0, &QueryFilter::default());
+    assert_eq!(objects(&hits), vec![near, far, wall]);
+
+    let ball = Collider::circle([0.0, 16.0].into(), 5.0);
+    let hits = ctx
+        .om
+        .shape_cast_all(&ball, &right, 1000.0, &QueryFilter::default());
+    assert!(hits.is_empty());
+
+    // One already overlapped is hit at distance 0.
+    let ball = Collider::circle([45.0, 0.0].into(), 8.0);
+    let hit = ctx
+        .om
+        .shape_cast(&ball, &right, 1000.0, &QueryFilter::default())
+        .unwrap();
+    assert_eq!((hit.object, hit.distance), (near, 0.0));
+}
@@ -0,0 +1,195 @@
+use arcamedia_engine::engine::context::EngineContext;
+use arcamedia_engine::engine::game_loop::GameLoop;
+use arcamedia_engine::engine::input::recording::{InputRecording, RecordedFrame};
+use arcamedia_engine::engine::input::touch::TouchPhase;
+use arcamedia_engine::engine::input::InputEvent;
+use arcamedia_engine::engine::object::snake_game::SnakeGame;
+use arcamedia_engine::engine::object_manager::ObjectManager;
+use arcamedia_engine::engine::renderer::headless::{CommandList, HeadlessRenderer, RenderCommand};
+use arcamedia_engine::engine::rng::RngState;
+use arcamedia_engine::engine::Engine;
+use arcamedia_engine::object;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+const FRAMES: u64 = 240;
+
+fn key(code: &str, down: bool) -> InputEvent {
+    InputEvent::Key {
+        code: code.into(),
+        down,
+    }
+}
+
+fn snake_game(seed: u64) -> (EngineContext, CommandList) {
+    let renderer = HeadlessRenderer::new();
+    let commands = renderer.commands();
+    let mut ctx = Engine::init_headless(renderer);
+    ctx.rng.reseed(seed);
+
+    let game = async_std::task::block_on(SnakeGame::new(&mut ctx, [1500.0, 900.0].into()));
+    object!(ctx, game);
+    ObjectManager::apply_pending(&mut ctx);
+
+    (ctx, commands)
+}
+
+// Steps and draws every frame, calling `input` first to push that frame's
+// events. Returns what was drawn each frame.
+fn run(
+    ctx: &mut EngineContext,
+    commands: &CommandList,
+    input: impl Fn(u64, &EngineContext),
+) -> Vec<Vec<RenderCommand>> {
+    let mut game_loop = GameLoop::new(60.0);
+    let mut frames = Vec::new();
+
+    for frame in 0..FRAMES {
+        input(frame, ctx);
+        game_loop.step_once(ctx);
+        game_loop.render(ctx, 1.0);
+        frames.push(commands.borrow_mut().drain(..).collect());
+    }
+
+    frames
+}
+
+// Clicks the start button, which drops an apple somewhere random, then
+// steers the snake around.
+fn play(frame: u64, ctx: &EngineContext) {
+    match frame {
+        2 => ctx.input.push_event(InputEvent::PointerMove {
+            x: 1500.0,
+            y: 540.0,
+        }),
+        3 => ctx.input.push_event(InputEvent::PointerButton {
+            button: 0,
+            down: true,
+        }),
+        4 => ctx.input.push_event(InputEvent::PointerButton {
+            button: 0,
+            down: false,
+        }),
+        30 => ctx.input.push_event(key("ArrowUp", true)),
+        32 => ctx.input.push_event(key("ArrowUp", false)),
+        90 => {
+            ctx.input.push_event(key("ArrowLeft", true));
+            ctx.input.push_event(key("ArrowLeft", false));
+        }
+        _ => {}
+    }
+}
+
+#[test]
+fn serialize_parse_round_trip() {
+    let recording = InputRecording {
+        rng: Some(RngState(u64::MAX)),
+        frame_count: 12,
+        frames: vec![
+            RecordedFrame {
+                frame: 0,
+                delta_time: 1.0 / 60.0,
+                events: vec![
+                    key("ArrowUp", true),
+                    InputEvent::KeyRepeat {
+                        code: "KeyA".into(),
+                    },
+                    InputEvent::Text { character: 'é' },
+                    InputEvent::Text { character: ' ' },
+                ],
+            },
+            RecordedFrame {
+                frame: 3,
+                delta_time: 0.1,
+                events: vec![
+                    InputEvent::PointerMove { x: -1.5, y: 0.25 },
+                    InputEvent::PointerButton {
+                        button: 2,
+                        down: true,
+                    },
+                    InputEvent::Wheel { delta: -120.0 },
+                    InputEvent::Touch {
+                        id: 7,
+                        phase: TouchPhase::Start,
+                        x: 10.0,
+                        y: 20.5,
+                    },
+                    InputEvent::Touch {
+                        id: 7,
+                        phase: TouchPhase::Cancel,
+                        x: 11.0,
+                        y: 21.0,
+                    },
+                ],
+            },
+            RecordedFrame {
+                frame: 11,
+                delta_time: 1.0 / 60.0,
+                events: vec![
+                    InputEvent::GamepadConnected { pad: 1 },
+                    InputEvent::GamepadButton {
+                        pad: 1,
+                        button: 12,
+                        value: 0.75,
+                    },
+                    InputEvent::GamepadAxis {
+                        pad: 1,
+                        axis: 0,
+                        value: -0.3,
+                    },
+                    InputEvent::GamepadDisconnected { pad: 1 },
+                ],
+            },
+        ],
+    };
+
+    assert_eq!(InputRecording::parse(&recording.serialize()), Ok(recording));
+
+    let unseeded = InputRecording::new(None);
+    assert_eq!(InputRecording::parse(&unseeded.serialize()), Ok(unseeded));
+
+    assert!(InputRecording::parse("").is_err());
+    assert!(InputRecording::parse("arcamedia-input 1\nrng x\nframes 0\n").is_err());
+}
+
+#[test]
+fn recorded_game_round_trips() {
+    let (mut ctx, commands) = snake_game(1);
+    ctx.start_recording();
+    run(&mut ctx, &commands, play);
+    let recording = ctx.stop_recording().unwrap();
+
+    assert_eq!(recording.frame_count, FRAMES);
+    assert_eq!(recording.frames.len(), 6);
+    assert_eq!(
+        InputRecording::parse(&recording.serialize()).as_ref(),
+        Ok(&recording)
+    );
+}
+
+#[test]
+fn replay_draws_the_same_frames() {
+    let (mut ctx, commands) = snake_game(1);
+    ctx.start_recording();
+    let recorded = run(&mut ctx, &commands, play);
+    let recording = InputRecording::parse(&ctx.stop_recording().unwrap().serialize()).unwrap();
+
+    // The start button was clicked, taking the buttons away and adding an
+    // apple.
+    assert_eq!(recorded[0].len(), 8);
+    assert_eq!(recorded[FRAMES as usize - 1].len(), 7);
+
+    let (mut ctx, commands) = snake_game(2);
+    ctx.replay(&recording);
+    let replayed = run(&mut ctx, &commands, |_, _| {});
+    assert_eq!(replayed, recorded);
+
+    // Without the recorded RNG state the apple lands elsewhere.
+    let (mut ctx, commands) = snake_game(2);
+    ctx.replay(&InputRecording {
+        rng: None,
+        ..recording
+    });
+    let unseeded = run(&mut ctx, &commands, |_, _| {});
+    assert_ne!(unseeded, recorded);
+}
@@ -0,0 +1,67 @@
+use arcamedia_engine::engine::rng::Rng;
+
+fn sequence(rng: &mut Rng) -> Vec<u64> {
+    (0..16).map(|_| rng.next_u64()).collect()
+}
+
+#[test]
+fn matches_reference_splitmix64() {
+    let mut rng = Rng::new(0);
+    assert_eq!(rng.next_u64(), 0xe220a8397b1dcdaf);
+    assert_eq!(rng.next_u64(), 0x6e789e6aa1b965f4);
+}
+
+#[test]
+fn same_seed_gives_same_sequence() {
+    assert_eq!(sequence(&mut Rng::new(42)), sequence(&mut Rng::new(42)));
+    assert_ne!(sequence(&mut Rng::new(42)), sequence(&mut Rng::new(43)));
+
+    let mut rng = Rng::new(7);
+    let first = sequence(&mut rng);
+    rng.reseed(7);
+    assert_eq!(sequence(&mut rng), first);
+}
+
+#[test]
+fn restore_replays_from_a_snapshot() {
+    let mut rng = Rng::new(1234);
+    rng.next_u64();
+
+    let state = rng.snapshot();
+    let mut items: Vec<u32> = (0..10).collect();
+    rng.shuffle(&mut items);
+    let picks = (
+        rng.range(-5, 5),
+        rng.float(),
+        rng.weighted_index(&[1.0, 2.0]),
+    );
+
+    rng.restore(state);
+    let mut replayed: Vec<u32> = (0..10).collect();
+    rng.shuffle(&mut replayed);
+    assert_eq!(replayed, items);
+    assert_eq!(
+        (
+            rng.range(-5, 5),
+            rng.float(),
+            rng.weighted_index(&[1.0, 2.0])
+        ),
+        picks
+    );
+}
+
+#[test]
+fn samples_stay_in_range() {
+    let mut rng = Rng::new(99);
+    for _ in 0..1000 {
+        assert!((-3..3).contains(&rng.range(-3, 3)));
+        assert!((0.0..1.0).contains(&rng.float()));
+        assert!((2.0..4.0).contains(&rng.float_range(2.0, 4.0)));
+        assert!(rng.index(5) < 5);
+        assert_eq!(rng.weighted_index(&[0.0, -1.0, 3.0]), Some(2));
+    }
+
+    assert_eq!(rng.range(4, 4), 4);
+    assert_eq!(rng.weighted_index(&[0.0, -1.0]), None);
+    assert_eq!(rng.choose::<u8>(&[]), None);
+}
@@ -0,0 +1,196 @@
+use arcamedia_engine::engine::physics::Physics;
+use arcamedia_engine::engine::shape::{Collider, Shape};
+use arcamedia_engine::engine::transform::Transform;
+use arcamedia_engine::engine::vec2f::Vec2f;
+
+fn assert_near(actual: &Vec2f, expected: [f64; 2]) {
+    assert!(
+        (actual.x - expected[0]).abs() < 1e-6 && (actual.y - expected[1]).abs() < 1e-6,
+        "{actual:?} is not near {expected:?}"
+    );
+}
+
+fn rect(position: [f64; 2], size: [f64; 2]) -> Collider {
+    Collider::rect(&Transform::new(position.into(), size.into(), 0.0))
+}
+
+// A 45 degree slope rising to the right from (-9.63, 0).
+fn ramp() -> Collider {
+    Shape::Polygon {
+        points: vec![
+            Vec2f::new(-0.5, -0.5),
+            Vec2f::new(0.5, -0.5),
+            Vec2f::new(0.5, 0.5),
+        ],
+    }
+    .collider(&Transform::new(
+        [40.37, 50.0].into(),
+        [100.0, 100.0].into(),
+        0.0,
+    ))
+}
+
+#[test]
+fn circles_overlap_within_their_radii() {
+    let a = Collider::circle([0.0, 0.0].into(), 1.0);
+
+    assert!(a.overlaps(&Collider::circle([1.5, 0.0].into(), 1.0)));
+    assert!(!a.overlaps(&Collider::circle([2.5, 0.0].into(), 1.0)));
+
+    let manifold = a
+        .collide(&Collider::circle([0.0, 1.5].into(), 1.0))
+        .unwrap();
+    assert_near(&manifold.normal, [0.0, 1.0]);
+    assert!((manifold.depth - 0.5).abs() < 1e-9);
+    assert_near(&manifold.points[0], [0.0, 0.75]);
+}
+
+#[test]
+fn capsule_reaches_along_its_length() {
+    let capsule = Shape::Capsule {
+        half_length: 0.5,
+        radius: 0.25,
+    }
+    .collider(&Transform::new([0.0, 0.0].into(), [4.0, 4.0].into(), 0.0));
+    assert_eq!(
+        capsule.points,
+        vec![Vec2f::new(-2.0, 0.0), Vec2f::new(2.0, 0.0)]
+    );
+    assert_eq!(capsule.radius, 1.0);
+
+    assert!(capsule.overlaps(&Collider::circle([1.9, 1.5].into(), 0.6)));
+    assert!(!capsule.overlaps(&Collider::circle([1.9, 1.7].into(), 0.6)));
+    assert!(capsule.contains_point(&[-2.5, 0.5].into()));
+    assert!(!capsule.contains_point(&[-2.9, 0.9].into()));
+
+    let manifold = capsule.collide(&rect([0.0, -1.5], [10.0, 1.0])).unwrap();
+    assert_near(&manifold.normal, [0.0, -1.0]);
+    assert!((manifold.depth - 0.0).abs() < 1e-9);
+}
+
+#[test]
+fn polygon_is_placed_by_the_transform_in_either_winding() {
+    let transform = Transform::new([10.0, 0.0].into(), [2.0, 4.0].into(), 0.0);
+    let triangle = |points: &[[f64; 2]]| {
+        Shape::Polygon {
+            points: points.iter().map(|point| (*point).into()).collect(),
+        }
+        .collider(&transform)
+    };
+    let clockwise = triangle(&[[-0.5, -0.5], [0.0, 0.5], [0.5, -0.5]]);
+    let counter_clockwise = triangle(&[[-0.5, -0.5], [0.5, -0.5], [0.0, 0.5]]);
+
+    assert_eq!(clockwise.points[1], Vec2f::new(10.0, 2.0));
+
+    for polygon in [&clockwise, &counter_clockwise] {
+        assert!(polygon.contains_point(&[10.0, 0.0].into()));
+        assert!(!polygon.contains_point(&[10.9, 1.9].into()));
+        assert!(polygon.overlaps(&rect([11.0, -2.0], [1.0, 1.0])));
+        assert!(!polygon.overlaps(&rect([11.5, 1.5], [1.0, 1.0])));
+    }
+}
+
+#[test]
+fn bounding_boxes_cover_points_and_radius() {
+    let (min, max) = Collider::circle([1.0, 2.0].into(), 3.0).bounding_box();
+    assert_eq!((min, max), ([-2.0, -1.0].into(), [4.0, 5.0].into()));
+
+    let (min, max) = Shape::Box
+        .collider(&Transform::new(
+            [0.0, 0.0].into(),
+            [2.0, 2.0].into(),
+            std::f64::consts::FRAC_PI_4,
+        ))
+        .bounding_box();
+    let half_diagonal = 2.0f64.sqrt();
+    assert_near(&min, [-half_diagonal, -half_diagonal]);
+    assert_near(&max, [half_diagonal, half_diagonal]);
+
+    let (min, max) = Shape::Capsule {
+        half_length: 1.0,
+        radius: 0.5,
+    }
+    .collider(&Transform::new([0.0, 0.0].into(), [2.0, 1.0].into(), 0.0))
+    .bounding_box();
+    assert_eq!((min, max), ([-2.5, -0.5].into(), [2.5, 0.5].into()));
+}
+
+#[test]
+fn crossing_hulls_fall_back_to_separating_axes() {
+    let manifold = rect([0.0, 0.0], [10.0, 10.0])
+        .collide(&rect([8.0, 1.0], [10.0, 10.0]))
+        .unwrap();
+    assert_near(&manifold.normal, [1.0, 0.0]);
+    assert!((manifold.depth - 2.0).abs() < 1e-9);
+    assert_eq!(manifold.points.len(), 2);
+
+    // A circle whose center is inside the box is pushed out of the nearest
+    // side, by the overlap plus its radius.
+    let manifold = rect([0.0, 0.0], [10.0, 10.0])
+        .collide(&Collider::circle([0.0, -4.0].into(), 2.0))
+        .unwrap();
+    assert_near(&manifold.normal, [0.0, -1.0]);
+    assert!((manifold.depth - 3.0).abs() < 1e-9);
+}
+
+#[test]
+fn empty_collider_touches_nothing() {
+    let empty = Collider::new(Vec::new(), 1.0);
+    let other = rect([0.0, 0.0], [10.0, 10.0]);
+
+    assert!(!empty.overlaps(&other));
+    assert!(other.collide(&empty).is_none());
+    assert!(empty.sweep(&[5.0, 0.0].into(), &[other]).is_none());
+}
+
+#[test]
+fn sweep_onto_a_floor_hits_its_top() {
+    let floor = rect([0.0, -50.0], [400.0, 100.0]);
+
+    let hit = rect([3.0, 37.0], [20.0, 20.0])
+        .sweep(&[10.0, -50.0].into(), &[floor])
+        .unwrap();
+
+    assert!((hit.time - 0.54).abs() < 1e-6);
+    assert_near(&hit.normal, [0.0, -1.0]);
+}
+
+#[test]
+fn sweep_into_a_ramp_hits_its_slope() {
+    let hit = rect([-20.0, 5.01], [10.0, 10.0])
+        .sweep(&[20.0, 0.0].into(), &[ramp()])
+        .unwrap();
+
+    let diagonal = 0.5f64.sqrt();
+    assert!((hit.time - 5.38 / 20.0).abs() < 1e-6);
+    assert_near(&hit.normal, [diagonal, -diagonal]);
+}
+
+#[test]
+fn sweep_that_misses_or_moves_away_hits_nothing() {
+    let floor = rect([0.0, -50.0], [400.0, 100.0]);
+    let resting = rect([0.0, 10.0], [20.0, 20.0]);
+
+    assert!(rect([0.0, 37.0], [20.0, 20.0])
+        .sweep(&[0.0, -20.0].into(), std::slice::from_ref(&floor))
+        .is_none());
+    assert!(resting
+        .sweep(&[0.0, 5.0].into(), std::slice::from_ref(&floor))
+        .is_none());
+    assert!(resting.sweep(&[5.0, 0.0].into(), &[floor]).is_none());
+}
+
+#[test]
+fn move_and_slide_climbs_a_ramp() {
+    let mut transform = Transform::new([-20.0, 5.01].into(), [10.0, 10.0].into(), 0.0);
+    let mut velocity = Vec2f::new(100.0, 0.0);
+
+    let hits = Physics::move_and_slide(&mut transform, &Shape::Box, &mut velocity, 0.2, &[ramp()]);
+
+    assert_eq!(hits.len(), 1);
+    assert_near(&velocity, [50.0, 50.0]);
+    // 5.38 along to the slope, then the rest turned up it.
+    assert!((transform.position.x - -7.31).abs() < 0.01);
+    assert!((transform.position.y - 12.32).abs() < 0.01);
+    assert!(!Collider::rect(&transform).overlaps(&ramp()));
+}
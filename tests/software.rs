@@ -0,0 +1,97 @@
+use arcamedia_engine::engine::game_loop::GameLoop;
+use arcamedia_engine::engine::object::rect::Rect;
+use arcamedia_engine::engine::object_manager::ObjectManager;
+use arcamedia_engine::engine::renderer::software::SoftwareRenderer;
+use arcamedia_engine::engine::renderer::{Uniform, VertexLayout};
+use arcamedia_engine::engine::transform::Transform;
+use arcamedia_engine::engine::Engine;
+use arcamedia_engine::object;
+use image::{Rgba, RgbaImage};
+use std::cell::RefCell;
+use std::rc::Rc;
+
+#[rustfmt::skip]
+const QUAD: [f32; 12] = [
+    -0.5, -0.5, 0.5, -0.5, -0.5, 0.5,
+    0.5, -0.5, 0.5, 0.5, -0.5, 0.5,
+];
+
+#[rustfmt::skip]
+const TEXTURED_QUAD: [f32; 24] = [
+    -0.5, -0.5, 0.0, 0.0,
+    0.5, -0.5, 1.0, 0.0,
+    -0.5, 0.5, 0.0, 1.0,
+    0.5, -0.5, 1.0, 0.0,
+    0.5, 0.5, 1.0, 1.0,
+    -0.5, 0.5, 0.0, 1.0,
+];
+
+#[test]
+fn quads_land_where_the_shaders_put_them() {
+    let mut ctx = Engine::init_headless(SoftwareRenderer::new(192, 108));
+    let renderer = ctx.renderer.as_mut();
+
+    let shader = renderer.create_shader("", "").unwrap();
+    let quad = renderer.create_mesh(&QUAD, VertexLayout::Position);
+    let textured_quad = renderer.create_mesh(&TEXTURED_QUAD, VertexLayout::PositionTexCoord);
+    let green = renderer.create_texture(&RgbaImage::from_pixel(1, 1, Rgba([0, 255, 0, 255])));
+
+    renderer.clear([0.0, 0.0, 0.0, 1.0]);
+    renderer.bind_shader(shader);
+    renderer.uniform(shader, "fragColor", Uniform::Vec4([1.0, 0.0, 0.0, 1.0]));
+    renderer.uniform_transform(
+        shader,
+        &Transform::new([0.0, 0.0].into(), [100.0, 100.0].into(), 0.0),
+    );
+    renderer.draw_mesh(quad);
+
+    renderer.bind_texture(green);
+    renderer.uniform(shader, "fragColor", Uniform::Vec4([1.0, 1.0, 1.0, 1.0]));
+    renderer.uniform_transform(
+        shader,
+        &Transform::new([480.0, 0.0].into(), [100.0, 100.0].into(), 0.0),
+    );
+    renderer.draw_mesh(textured_quad);
+
+    let frame = renderer.frame().unwrap();
+    assert_eq!((frame.width(), frame.height()), (192, 108));
+    assert_eq!(frame.get_pixel(96, 54).0, [255, 0, 0, 255]);
+    assert_eq!(frame.get_pixel(144, 54).0, [0, 255, 0, 255]);
+    assert_eq!(frame.get_pixel(5, 5).0, [0, 0, 0, 255]);
+    assert_eq!(frame.get_pixel(120, 54).0, [0, 0, 0, 255]);
+
+    let png = ObjectManager::capture_png(&ctx).unwrap();
+    let decoded = image::load_from_memory(&png).unwrap().to_rgba8();
+    assert_eq!(decoded, frame);
+}
+
+#[test]
+fn red_rect_covers_the_center_only() {
+    let mut ctx = Engine::init_headless(SoftwareRenderer::new(192, 108));
+
+    object!(
+        ctx,
+        Rect::new(
+            [0.0, 0.0].into(),
+            [100.0, 100.0].into(),
+            0.0,
+            [1.0, 0.0, 0.0, 1.0],
+            false,
+        )
+    );
+    ObjectManager::apply_pending(&mut ctx);
+
+    let mut game_loop = GameLoop::new(60.0);
+    game_loop.step_once(&mut ctx);
+    game_loop.render(&mut ctx, 1.0);
+
+    let frame = ctx.renderer.frame().unwrap();
+    assert_eq!((frame.width(), frame.height()), (192, 108));
+    assert_eq!(frame.get_pixel(96, 54).0, [255, 0, 0, 255]);
+    assert_eq!(frame.get_pixel(5, 5).0, [0, 0, 0, 255]);
+    assert_eq!(frame.get_pixel(120, 54).0, [0, 0, 0, 255]);
+
+    let png = ObjectManager::capture_png(&ctx).unwrap();
+    let decoded = image::load_from_memory(&png).unwrap().to_rgba8();
+    assert_eq!(decoded, frame);
+}
@@ -0,0 +1,203 @@
+use arcamedia_engine::engine::context::EngineContext;
+use arcamedia_engine::engine::object::rect::Rect;
+use arcamedia_engine::engine::object_manager::{ObjectId, ObjectManager};
+use arcamedia_engine::engine::renderer::headless::HeadlessRenderer;
+use arcamedia_engine::engine::rng::Rng;
+use arcamedia_engine::engine::spatial_hash::SpatialHash;
+use arcamedia_engine::engine::transform::Transform;
+use arcamedia_engine::engine::vec2f::Vec2f;
+use arcamedia_engine::engine::Engine;
+use arcamedia_engine::object;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+fn random_transform(rng: &mut Rng) -> Transform {
+    // Now and then one big enough to skip the grid cells.
+    let size = match rng.chance(0.05) {
+        true => rng.float_range(9000.0, 20000.0),
+        false => rng.float_range(1.0, 300.0),
+    };
+
+    Transform::new(
+        [
+            rng.float_range(-2000.0, 2000.0),
+            rng.float_range(-2000.0, 2000.0),
+        ]
+        .into(),
+        [size, rng.float_range(1.0, 300.0)].into(),
+        rng.float_range(0.0, std::f64::consts::TAU),
+    )
+}
+
+fn overlaps(a: &(Vec2f, Vec2f), b: &(Vec2f, Vec2f)) -> bool {
+    a.0.x < b.1.x && a.1.x > b.0.x && a.0.y < b.1.y && a.1.y > b.0.y
+}
+
+fn spawn(ctx: &mut EngineContext, transform: &Transform) -> ObjectId {
+    object!(
+        ctx,
+        Rect::new(
+            transform.position.clone(),
+            transform.size.clone(),
+            transform.rotation,
+            [1.0, 1.0, 1.0, 1.0],
+            false,
+        )
+    )
+}
+
+fn set_transform(ctx: &EngineContext, id: ObjectId, transform: &Transform) {
+    let object = ctx.om.get(id).unwrap();
+    *object.borrow_mut().transform_mut().unwrap() = transform.clone();
+}
+
+// `objects_in_bounds` for a handful of random areas, checked against every
+// live object.
+fn assert_matches_brute_force(ctx: &EngineContext, truth: &[(ObjectId, Transform)], rng: &mut Rng) {
+    for _ in 0..50 {
+        let area = Transform::new(
+            [
+                rng.float_range(-2500.0, 2500.0),
+                rng.float_range(-2500.0, 2500.0),
+            ]
+            .into(),
+            [rng.float_range(0.0, 1500.0), rng.float_range(0.0, 1500.0)].into(),
+            0.0,
+        );
+        let half_size = &area.size / 2.0;
+        let area_bounds = (&area.position - &half_size, &area.position + &half_size);
+
+        let expected: Vec<ObjectId> = truth
+            .iter()
+            .filter(|(_, transform)| overlaps(&transform.bounding_box(), &area_bounds))
+            .map(|(id, _)| *id)
+            .collect();
+
+        assert_eq!(ctx.om.objects_in_bounds(&area), expected);
+    }
+}
+
+#[test]
+fn query_finds_everything_brute_force_does() {
+    let mut ctx = Engine::init_headless(HeadlessRenderer::new());
+    let mut rng = Rng::new(18);
+    let mut hash = SpatialHash::new(128.0);
+
+    let mut boxes: Vec<(ObjectId, (Vec2f, Vec2f))> = Vec::new();
+    for _ in 0..300 {
+        let bounds = random_transform(&mut rng).bounding_box();
+        // Only spawned for its id.
+        let id = spawn(&mut ctx, &random_transform(&mut rng));
+        hash.update(id, Some(bounds.clone()));
+        boxes.push((id, bounds));
+    }
+
+    for _ in 0..100 {
+        let i = rng.index(boxes.len());
+        match rng.chance(0.3) {
+            true => {
+                hash.remove(boxes.swap_remove(i).0);
+            }
+            false => {
+                boxes[i].1 = random_transform(&mut rng).bounding_box();
+                hash.update(boxes[i].0, Some(boxes[i].1.clone()));
+            }
+        }
+    }
+
+    for _ in 0..200 {
+        let min: Vec2f = [
+            rng.float_range(-2500.0, 2500.0),
+            rng.float_range(-2500.0, 2500.0),
+        ]
+        .into();
+        let max: Vec2f = [
+            min.x + rng.float_range(0.0, 1500.0),
+            min.y + rng.float_range(0.0, 1500.0),
+        ]
+        .into();
+
+        let found = hash.query(&min, &max);
+
+        let mut sorted = found.clone();
+        sorted.sort();
+        sorted.dedup();
+        assert_eq!(found, sorted);
+
+        for (id, bounds) in &boxes {
+            if overlaps(bounds, &(min.clone(), max.clone())) {
+                assert!(found.contains(id));
+            }
+        }
+        for id in &found {
+            assert!(boxes.iter().any(|(other, _)| other == id));
+        }
+    }
+
+    hash.clear();
+    assert!(hash
+        .query(&[-1e6, -1e6].into(), &[1e6, 1e6].into())
+        .is_empty());
+}
+
+#[test]
+fn objects_in_bounds_matches_brute_force() {
+    let mut ctx = Engine::init_headless(HeadlessRenderer::new());
+    let mut rng = Rng::new(2018);
+
+    let mut truth: Vec<(ObjectId, Transform)> = Vec::new();
+    for _ in 0..300 {
+        let transform = random_transform(&mut rng);
+        truth.push((spawn(&mut ctx, &transform), transform));
+    }
+    ObjectManager::apply_pending(&mut ctx);
+    ObjectManager::tick(&mut ctx, 0.0);
+    assert_matches_brute_force(&ctx, &truth, &mut rng);
+
+    for round in 0..5 {
+        for _ in 0..40 {
+            let i = rng.index(truth.len());
+            let transform = random_transform(&mut rng);
+            set_transform(&ctx, truth[i].0, &transform);
+            truth[i].1 = transform;
+        }
+
+        for _ in 0..10 {
+            let (id, _) = truth.remove(rng.index(truth.len()));
+            ctx.om.despawn(id);
+        }
+
+        // Respawns reuse the freed slots, but still come last.
+        for _ in 0..10 {
+            let transform = random_transform(&mut rng);
+            truth.push((spawn(&mut ctx, &transform), transform));
+        }
+
+        ObjectManager::apply_pending(&mut ctx);
+        if round % 2 == 0 {
+            ObjectManager::tick(&mut ctx, 0.0);
+        }
+        assert_matches_brute_force(&ctx, &truth, &mut rng);
+    }
+}
+
+#[test]
+fn moves_through_objects_are_seen_before_the_next_tick() {
+    let mut ctx = Engine::init_headless(HeadlessRenderer::new());
+    let mut rng = Rng::new(7);
+
+    let mut truth: Vec<(ObjectId, Transform)> = Vec::new();
+    for _ in 0..100 {
+        let transform = random_transform(&mut rng);
+        truth.push((spawn(&mut ctx, &transform), transform));
+    }
+    ObjectManager::apply_pending(&mut ctx);
+    ObjectManager::tick(&mut ctx, 0.0);
+
+    for ((_, object), (_, transform)) in ctx.om.objects().zip(truth.iter_mut()) {
+        *transform = random_transform(&mut rng);
+        *object.borrow_mut().transform_mut().unwrap() = transform.clone();
+    }
+
+    assert_matches_brute_force(&ctx, &truth, &mut rng);
+}
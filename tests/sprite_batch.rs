@@ -0,0 +1,131 @@
+use arcamedia_engine::engine::game_loop::GameLoop;
+use arcamedia_engine::engine::object::rect::Rect;
+use arcamedia_engine::engine::object::textured_rect::TexturedRect;
+use arcamedia_engine::engine::object_manager::ObjectManager;
+use arcamedia_engine::engine::renderer::headless::{HeadlessRenderer, RenderCommand};
+use arcamedia_engine::engine::sprite_batch::{SpriteBatch, FULL_UV};
+use arcamedia_engine::engine::texture::Texture;
+use arcamedia_engine::engine::transform::Transform;
+use arcamedia_engine::engine::Engine;
+use arcamedia_engine::object;
+use image::RgbaImage;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+const WHITE: [f32; 4] = [1.0, 1.0, 1.0, 1.0];
+
+fn quad(x: f64) -> Transform {
+    Transform::new([x, 0.0].into(), [10.0, 10.0].into(), 0.0)
+}
+
+#[test]
+fn same_texture_rects_draw_in_one_call() {
+    let renderer = HeadlessRenderer::new();
+    let commands = renderer.commands();
+    let mut ctx = Engine::init_headless(renderer);
+
+    for index in 0..100 {
+        object!(
+            ctx,
+            Rect::new(
+                [index as f64 * 5.0, 0.0].into(),
+                [4.0, 4.0].into(),
+                0.0,
+                WHITE,
+                false,
+            )
+        );
+    }
+    ObjectManager::apply_pending(&mut ctx);
+
+    let mut game_loop = GameLoop::new(60.0);
+    game_loop.step_once(&mut ctx);
+    ctx.batch.take_draw_calls();
+    game_loop.render(&mut ctx, 0.0);
+
+    assert_eq!(ctx.batch.take_draw_calls(), 1);
+    let sprites = commands
+        .borrow()
+        .iter()
+        .filter(|command| matches!(command, RenderCommand::Sprite(_)))
+        .count();
+    assert_eq!(sprites, 100);
+}
+
+#[test]
+fn switching_texture_starts_a_new_call() {
+    let renderer = HeadlessRenderer::new();
+    let commands = renderer.commands();
+    let mut ctx = Engine::init_headless(renderer);
+    let texture = Texture::from_image(ctx.renderer.as_mut(), &RgbaImage::new(2, 2));
+
+    // Plain, plain, textured, textured, plain: three runs.
+    for (index, textured) in [false, false, true, true, false].into_iter().enumerate() {
+        let position = [index as f64 * 20.0, 0.0].into();
+        let size = [10.0, 10.0].into();
+        if textured {
+            object!(
+                ctx,
+                TexturedRect::new(position, size, 0.0, WHITE, texture.clone(), false)
+            );
+        } else {
+            object!(ctx, Rect::new(position, size, 0.0, WHITE, false));
+        }
+    }
+    ObjectManager::apply_pending(&mut ctx);
+
+    let mut game_loop = GameLoop::new(60.0);
+    game_loop.step_once(&mut ctx);
+    ctx.batch.take_draw_calls();
+    game_loop.render(&mut ctx, 0.0);
+
+    assert_eq!(ctx.batch.take_draw_calls(), 3);
+    let textures: Vec<Option<i32>> = commands
+        .borrow()
+        .iter()
+        .filter_map(|command| match command {
+            RenderCommand::Sprite(sprite) => Some(sprite.texture),
+            _ => None,
+        })
+        .collect();
+    let white = textures[0];
+    assert_ne!(white, Some(texture.id()));
+    assert_eq!(
+        textures,
+        vec![white, white, Some(texture.id()), Some(texture.id()), white]
+    );
+}
+
+#[test]
+fn full_batch_flushes_early() {
+    let mut renderer = HeadlessRenderer::new();
+    let commands = renderer.commands();
+    let mut batch = SpriteBatch::new(&mut renderer);
+
+    for index in 0..4096 {
+        batch.draw(&mut renderer, &quad(index as f64), WHITE, None, FULL_UV);
+    }
+    assert_eq!(batch.take_draw_calls(), 0);
+    assert!(batch.flush(&mut renderer));
+    assert_eq!(batch.take_draw_calls(), 1);
+    assert!(!batch.flush(&mut renderer));
+
+    for index in 0..4097 {
+        batch.draw(&mut renderer, &quad(index as f64), WHITE, None, FULL_UV);
+    }
+    assert_eq!(batch.take_draw_calls(), 1);
+    batch.flush(&mut renderer);
+    assert_eq!(batch.take_draw_calls(), 1);
+
+    // Every quad is drawn once, in order.
+    let commands = commands.borrow();
+    let xs: Vec<f64> = commands
+        .iter()
+        .filter_map(|command| match command {
+            RenderCommand::Sprite(sprite) => Some(sprite.transform.position.x),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(xs.len(), 4096 + 4097);
+    assert!(xs[4096..].iter().copied().eq((0..4097).map(f64::from)));
+}
@@ -0,0 +1,153 @@
+use arcamedia_engine::engine::input::{Input, InputEvent, Modifiers};
+
+fn key(code: &str, down: bool) -> InputEvent {
+    InputEvent::Key {
+        code: code.into(),
+        down,
+    }
+}
+
+fn repeat(code: &str) -> InputEvent {
+    InputEvent::KeyRepeat { code: code.into() }
+}
+
+fn text(character: char) -> InputEvent {
+    InputEvent::Text { character }
+}
+
+// Runs one frame of `events` against a fresh frame's edge maps.
+fn frame(input: &mut Input, events: Vec<InputEvent>) {
+    input.flush_pressed_map();
+    for event in events {
+        input.push_event(event);
+    }
+    input.poll_events(1.0 / 60.0);
+}
+
+#[test]
+fn edit_text_applies_typing_and_backspace_in_order() {
+    let mut input = Input::new();
+    let mut name = String::from("x");
+
+    frame(
+        &mut input,
+        vec![
+            text('a'),
+            text('b'),
+            key("Backspace", true),
+            key("Backspace", false),
+            text('c'),
+        ],
+    );
+    assert_eq!(input.typed_text(), "abc");
+    input.edit_text(&mut name, 10);
+    assert_eq!(name, "xac");
+
+    // Held down, each repeat deletes another.
+    frame(
+        &mut input,
+        vec![key("Backspace", true), repeat("Backspace")],
+    );
+    frame(&mut input, vec![repeat("Backspace")]);
+    let mut name = String::from("abcd");
+    input.edit_text(&mut name, 10);
+    assert_eq!(name, "abc");
+
+    // Edits are gone the next frame.
+    frame(&mut input, Vec::new());
+    input.edit_text(&mut name, 10);
+    assert_eq!(name, "abc");
+}
+
+#[test]
+fn edit_text_stops_typing_at_the_limit() {
+    let mut input = Input::new();
+    let mut name = String::from("ab");
+
+    frame(
+        &mut input,
+        vec![
+            text('c'),
+            text('d'),
+            key("Backspace", true),
+            text('e'),
+            text('f'),
+        ],
+    );
+    input.edit_text(&mut name, 3);
+    assert_eq!(name, "abe");
+}
+
+#[test]
+fn on_screen_backspace_deletes() {
+    let mut input = Input::new();
+    let mut name = String::from("ab");
+
+    input.set_virtual_key("Backspace", true);
+    frame(&mut input, Vec::new());
+    input.edit_text(&mut name, 10);
+    assert_eq!(name, "a");
+}
+
+#[test]
+fn repeats_count_only_while_held_and_after_the_press() {
+    let mut input = Input::new();
+
+    frame(&mut input, vec![repeat("ArrowDown")]);
+    assert!(!input.key_was_repeated("ArrowDown"));
+
+    frame(&mut input, vec![key("ArrowDown", true)]);
+    assert!(!input.key_was_repeated("ArrowDown"));
+    assert!(input.key_was_pressed_or_repeated("ArrowDown"));
+
+    frame(&mut input, Vec::new());
+    assert!(!input.key_was_pressed_or_repeated("ArrowDown"));
+
+    frame(&mut input, vec![repeat("ArrowDown")]);
+    assert!(input.key_was_repeated("ArrowDown"));
+    assert!(input.key_was_pressed_or_repeated("ArrowDown"));
+
+    frame(
+        &mut input,
+        vec![key("ArrowDown", false), repeat("ArrowDown")],
+    );
+    assert!(!input.key_was_repeated("ArrowDown"));
+}
+
+#[test]
+fn modifiers_count_either_side() {
+    let mut input = Input::new();
+    assert_eq!(input.modifiers(), Modifiers::default());
+
+    frame(
+        &mut input,
+        vec![key("ShiftRight", true), key("ControlLeft", true)],
+    );
+    assert_eq!(
+        input.modifiers(),
+        Modifiers {
+            shift: true,
+            control: true,
+            alt: false,
+            meta: false,
+        }
+    );
+
+    frame(
+        &mut input,
+        vec![
+            key("ShiftRight", false),
+            key("AltLeft", true),
+            key("MetaRight", true),
+        ],
+    );
+    assert_eq!(
+        input.modifiers(),
+        Modifiers {
+            shift: false,
+            control: true,
+            alt: true,
+            meta: true,
+        }
+    );
+}
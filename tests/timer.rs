@@ -0,0 +1,38 @@
+use arcamedia_engine::engine::clock::{Clock, ManualClock};
+use arcamedia_engine::engine::game_loop::GameLoop;
+use arcamedia_engine::engine::renderer::headless::HeadlessRenderer;
+use arcamedia_engine::engine::timer::Timer;
+use arcamedia_engine::engine::Engine;
+
+#[test]
+fn timer_follows_a_manual_clock() {
+    let clock = ManualClock::new();
+    clock.set(1000.0);
+    let mut timer = Timer::with_clock(clock.clone());
+    assert_eq!(timer.elapsed(), 0.0);
+
+    clock.advance(250.0);
+    assert_eq!(timer.elapsed(), 250.0);
+    assert_eq!(timer.elapsed(), 250.0);
+
+    assert_eq!(timer.elapsed_reset(), 250.0);
+    assert_eq!(timer.elapsed(), 0.0);
+
+    clock.advance(40.0);
+    assert_eq!(timer.elapsed_reset(), 40.0);
+}
+
+#[test]
+fn game_loop_steps_drive_the_context_clock() {
+    let mut ctx = Engine::init_headless(HeadlessRenderer::new());
+    let timer = Timer::with_clock(ctx.clock.clone());
+    let mut game_loop = GameLoop::new(4.0);
+
+    game_loop.step_once(&mut ctx);
+    game_loop.step_once(&mut ctx);
+    assert_eq!(timer.elapsed(), 500.0);
+    assert_eq!(ctx.clock.now(), 500.0);
+
+    game_loop.advance(&mut ctx, 0.375);
+    assert_eq!(timer.elapsed(), 750.0);
+}
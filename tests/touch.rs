@@ -0,0 +1,97 @@
+use arcamedia_engine::engine::input::touch::{SwipeDirection, TouchPhase, Touches};
+use arcamedia_engine::engine::input::{Input, InputEvent};
+use arcamedia_engine::engine::vec2f::Vec2f;
+
+// Drags a finger from `start` to `end` over `time` seconds and returns the
+// swipe it made, if any.
+fn drag(start: [f64; 2], end: [f64; 2], time: f64) -> Option<SwipeDirection> {
+    let mut touches = Touches::new();
+    touches.apply(1, TouchPhase::Start, start.into());
+    touches.age(time);
+    touches.apply(1, TouchPhase::End, end.into());
+
+    assert!(touches.swipes.len() <= 1);
+    touches.swipes.first().map(|swipe| swipe.direction)
+}
+
+#[test]
+fn swipe_goes_the_way_it_moved_most() {
+    assert_eq!(
+        drag([200.0, 200.0], [200.0, 100.0], 0.2),
+        Some(SwipeDirection::Up)
+    );
+    assert_eq!(
+        drag([200.0, 200.0], [230.0, 300.0], 0.2),
+        Some(SwipeDirection::Down)
+    );
+    assert_eq!(
+        drag([200.0, 200.0], [100.0, 150.0], 0.2),
+        Some(SwipeDirection::Left)
+    );
+    assert_eq!(
+        drag([200.0, 200.0], [300.0, 250.0], 0.2),
+        Some(SwipeDirection::Right)
+    );
+}
+
+#[test]
+fn short_or_slow_drag_is_no_swipe() {
+    // Thresholds are 80 pixels and half a second.
+    assert_eq!(drag([200.0, 200.0], [200.0, 121.0], 0.2), None);
+    assert_eq!(
+        drag([200.0, 200.0], [200.0, 120.0], 0.2),
+        Some(SwipeDirection::Up)
+    );
+    assert_eq!(drag([200.0, 200.0], [200.0, 100.0], 0.6), None);
+    assert_eq!(
+        drag([200.0, 200.0], [200.0, 100.0], 0.5),
+        Some(SwipeDirection::Up)
+    );
+}
+
+#[test]
+fn cancelled_touch_is_no_swipe() {
+    let mut touches = Touches::new();
+    touches.apply(1, TouchPhase::Start, [200.0, 200.0].into());
+    touches.apply(1, TouchPhase::Cancel, [200.0, 0.0].into());
+
+    assert!(touches.swipes.is_empty());
+    assert_eq!(touches.ended.len(), 1);
+    assert!(touches.active.is_empty());
+}
+
+#[test]
+fn swipe_taps_its_key_and_action() {
+    let mut input = Input::new();
+    let touch = |phase, y| InputEvent::Touch {
+        id: 3,
+        phase,
+        x: 150.0,
+        y,
+    };
+
+    input.push_event(touch(TouchPhase::Start, 400.0));
+    input.poll_events(0.1);
+    input.flush_pressed_map();
+    input.push_event(touch(TouchPhase::Move, 300.0));
+    input.push_event(touch(TouchPhase::End, 250.0));
+    input.poll_events(0.1);
+
+    let swipe = &input.touches.swipes[0];
+    assert_eq!(swipe.direction, SwipeDirection::Up);
+    assert_eq!(swipe.start, Vec2f::new(150.0, 400.0));
+    assert_eq!(swipe.end, Vec2f::new(150.0, 250.0));
+
+    assert!(input.key_was_pressed("SwipeUp"));
+    assert!(input.key_was_released("SwipeUp"));
+    assert!(!input.get_key_down("SwipeUp"));
+    assert!(input.action_pressed("up"));
+    assert!(input.action_released("up"));
+    assert!(!input.action_down("up"));
+
+    // Gone the next frame.
+    input.flush_pressed_map();
+    input.poll_events(0.1);
+    assert!(input.touches.swipes.is_empty());
+    assert!(!input.action_pressed("up"));
+}